@@ -0,0 +1,188 @@
+//! Day 1: Calorie Counting
+//!
+//! Counts the calories carried by each elf.
+
+#![warn(missing_docs)]
+
+use aoc_common::{ParseError, Solution};
+
+pub mod report;
+pub mod top;
+use top::{ElfTotal, TopN};
+
+/// The inventory of an elf.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+  /// Position of the elf in the input, starting at 1.
+  pub index : usize,
+  /// Calories of each item, in input order.
+  pub items : Vec<u32>
+}
+
+impl Elf {
+
+  /// Returns the calories carried by the elf.
+  ///
+  /// Panics if they exceed u32::MAX, which [`parse_elves`] rejects.
+  pub fn total(&self) -> u32 {
+    self.items.iter().sum()
+  }
+}
+
+/// Reads the items of each elf, one calorie count per line.
+///
+/// Elves are separated by blank lines. The last elf does not need to be
+/// followed by a blank line.
+pub fn parse_elves(input : &str) -> Result<Vec<Elf>, ParseError> {
+  let mut elves : Vec<Elf> = Vec::new();
+  let mut items : Vec<u32> = Vec::new();
+  let mut total : u32 = 0;
+  for (index, line) in input.lines().enumerate() {
+      let value_str = line.trim_end();
+      if value_str.is_empty() {
+          // New Aelve
+          if !items.is_empty() {
+            elves.push(Elf { index: elves.len() + 1, items });
+            items = Vec::new();
+            total = 0;
+          }
+      }
+      else {
+          let line_value : u32 = value_str.parse()
+            .map_err(|_| ParseError::new(index + 1, 1, value_str, "Invalid calories"))?;
+          total = total.checked_add(line_value)
+            .ok_or_else(|| ParseError::new(index + 1, 1, value_str, "Calories overflow"))?;
+          items.push(line_value);
+      }
+  }
+  if !items.is_empty() {
+    elves.push(Elf { index: elves.len() + 1, items });
+  }
+  Ok(elves)
+}
+
+/// Inventories of all elves.
+#[derive(Debug)]
+pub struct Calories {
+  /// Elves, in input order.
+  pub elves : Vec<Elf>
+}
+
+impl Calories {
+
+  /// Returns all elves, the ones carrying the most calories first.
+  ///
+  /// Elves carrying the same calories keep their input order.
+  pub fn ranking(&self) -> Vec<&Elf> {
+    let mut ranking : Vec<&Elf> = self.elves.iter().collect();
+    ranking.sort_by_key(|elf| std::cmp::Reverse(elf.total()));
+    ranking
+  }
+
+  /// Selects the `filter_count` elves carrying the most calories.
+  pub fn select(&self, filter_count : usize) -> TopN {
+    self.select_into(TopN::new(filter_count))
+  }
+
+  /// Selects the `filter_count` elves carrying the most calories, remembering
+  /// all the ties at the boundary.
+  pub fn select_with_ties(&self, filter_count : usize) -> TopN {
+    self.select_into(TopN::with_ties(filter_count))
+  }
+
+  fn select_into(&self, mut top : TopN) -> TopN {
+    top.extend(self.elves.iter().map(|elf| ElfTotal { index: elf.index, total: elf.total() }));
+    top
+  }
+
+  fn get_elves(&self, selection : &[ElfTotal]) -> Vec<&Elf> {
+    selection.iter().map(|elf| &self.elves[elf.index - 1]).collect()
+  }
+
+  /// Returns the `filter_count` elves carrying the most calories, or all
+  /// elves if there are fewer.
+  pub fn top(&self, filter_count : usize) -> Vec<&Elf> {
+    self.get_elves(&self.select(filter_count).to_sorted_vec())
+  }
+
+  /// Returns the `filter_count` elves carrying the most calories, followed by
+  /// the other elves carrying as much as the last of them.
+  pub fn top_with_ties(&self, filter_count : usize) -> Vec<&Elf> {
+    let top = self.select_with_ties(filter_count);
+    let mut selection = top.to_sorted_vec();
+    let others : Vec<ElfTotal> = top.boundary_ties().into_iter()
+      .filter(|elf| !selection.contains(elf))
+      .collect();
+    selection.extend(others);
+    self.get_elves(&selection)
+  }
+
+  /// Returns the elves carrying as much as the elf ranked `filter_count`,
+  /// on both sides of the boundary of the top `filter_count`.
+  ///
+  /// Returns nothing if there are fewer elves.
+  pub fn boundary_ties(&self, filter_count : usize) -> Vec<&Elf> {
+    self.get_elves(&self.select_with_ties(filter_count).boundary_ties())
+  }
+
+  /// Returns the sum of calories carried by the `filter_count` elves carrying
+  /// the most.
+  pub fn top_total(&self, filter_count : usize) -> u64 {
+    self.select(filter_count).total()
+  }
+}
+
+impl Solution for Calories {
+  type Answer1 = u64;
+  type Answer2 = u64;
+
+  fn parse(input : &str) -> Result<Self, ParseError> {
+    Ok(Calories { elves: parse_elves(input)? })
+  }
+
+  fn part1(&self) -> u64 {
+    self.top_total(1)
+  }
+
+  fn part2(&self) -> u64 {
+    self.top_total(3)
+  }
+}
+
+#[test]
+fn test_example() {
+  let calories = Calories::parse("1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n\n")
+    .expect("Valid input");
+  assert_eq!(24000, calories.part1());
+  assert_eq!(45000, calories.part2());
+}
+
+#[test]
+fn test_last_elf_without_blank_line() {
+  let calories = Calories::parse("1000\n2000\n\n\n4000\n\n5000").expect("Valid input");
+  assert_eq!(3, calories.elves.len());
+  assert_eq!(Elf { index: 3, items: vec![5000] }, calories.elves[2]);
+  assert_eq!(vec![3, 2], calories.top(2).iter().map(|elf| elf.index).collect::<Vec<usize>>());
+  assert_eq!(3, calories.top(5).len());
+}
+
+#[test]
+fn test_ties() {
+  let calories = Calories::parse("5\n\n3\n\n1\n2\n\n3\n\n1").expect("Valid input");
+  let indexes = |elves : Vec<&Elf>| elves.iter().map(|elf| elf.index).collect::<Vec<usize>>();
+  assert_eq!(vec![1, 2, 3, 4], indexes(calories.top_with_ties(2)));
+  assert_eq!(vec![2, 3, 4], indexes(calories.boundary_ties(2)));
+  assert_eq!(vec![1], indexes(calories.top_with_ties(1)));
+  assert!(calories.boundary_ties(6).is_empty());
+}
+
+#[test]
+fn test_invalid_calories() {
+  let error = Calories::parse("1000\n\n2x00\n").expect_err("Invalid input");
+  assert_eq!(3, error.line);
+  assert_eq!("2x00", error.text);
+  let error = Calories::parse("4000000000\n\n4000000000\n300000000\n").expect_err("Overflow");
+  assert_eq!((4, "Calories overflow"), (error.line, error.message.as_str()));
+  let calories = Calories::parse("4000000000\n\n300000000\n").expect("Valid input");
+  assert_eq!(4300000000, calories.top_total(2));
+}
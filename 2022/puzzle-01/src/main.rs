@@ -1,62 +1,112 @@
 use std::env;
 use std::io;
+use std::process::ExitCode;
+
+use puzzle_01::report::report;
+use puzzle_01::top::{read_top, ElfTotals};
 
 static DEFAULT_FILTER : usize = 1;
+static DEFAULT_PERCENTILES : [f64; 4] = [25.0, 75.0, 90.0, 99.0];
+static DEFAULT_BINS : usize = 10;
+static HISTOGRAM_WIDTH : usize = 50;
 
-fn get_filter_count() -> usize {
-  let args: Vec<String> = env::args().collect();
-  if &args.len() > &1 {
-    let tmp = &args.get(1)
-      .map(|s| s.parse::<usize>())
-      .or(Option::Some(Result::Ok(DEFAULT_FILTER)))
-      .unwrap()
-      .expect("Internal error");
-    *tmp
-  }
-  else {
-    DEFAULT_FILTER
-  }
+/// Options of the report mode.
+struct ReportArgs {
+    percentiles : Vec<f64>,
+    bins : usize,
+    json : bool
 }
 
-fn sort_aelves(stdin : io::Stdin, counters : &mut Vec<u32>) -> () {
-  let mut line = String::new();
-  let mut count : u32 = 0;
-  loop {
-      let bytes_count = stdin.read_line(&mut line).unwrap();
-      //println!("{}", line);
-      let value_str = line.trim_end();
-      if bytes_count <= 0 {
-          break;
-      }
-      else if value_str.is_empty() {
-          // New Aelve
-          counters.push(count);
-          count = 0;
-      }
-      else {
-          //println!("{}", line);
-          let line_value : u32 = value_str.parse().expect("Failed to parse line");
-          count += line_value
-      }
-      line.clear();
-  }
+fn parse_report_args(args : &[String]) -> Result<ReportArgs, String> {
+    let mut report_args = ReportArgs { percentiles: DEFAULT_PERCENTILES.to_vec(), bins: DEFAULT_BINS, json: false };
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--percentiles" => {
+                report_args.percentiles = value()?.split(',')
+                    .map(|p| p.trim().parse::<f64>().ok().filter(|p| (0.0..=100.0).contains(p))
+                        .ok_or(format!("Invalid percentile {}", p)))
+                    .collect::<Result<Vec<f64>, String>>()?;
+            },
+            "--bins" => {
+                let bins = value()?;
+                report_args.bins = bins.parse().map_err(|_| format!("Invalid bin count {}", bins))?;
+            },
+            "--format" => {
+                report_args.json = match value()?.as_str() {
+                    "json" => true,
+                    "text" => false,
+                    other => return Err(format!("Unknown format {}", other))
+                };
+            },
+            _ => return Err(format!("Unknown option {}", arg))
+        }
+    }
+    Ok(report_args)
+}
 
-  counters.sort_by(|l, r| l.cmp(r).reverse());
+fn print_report(args : &[String]) -> ExitCode {
+    let report_args = match parse_report_args(args) {
+        Ok(report_args) => report_args,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!("Usage: puzzle-01 report [--percentiles 25,75] [--bins 10] [--format text|json]");
+            return ExitCode::FAILURE;
+        }
+    };
+    let totals = match ElfTotals::new(io::stdin().lock()).map(|elf| elf.map(|e| e.total)).collect::<Result<Vec<u32>, _>>() {
+        Ok(totals) => totals,
+        Err(error) => {
+            eprintln!("Invalid input at {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    match report(&totals, &report_args.percentiles, report_args.bins) {
+        None => {
+            eprintln!("No elf");
+            return ExitCode::FAILURE;
+        },
+        Some(report) if report_args.json => println!("{}", report.to_json()),
+        Some(report) => println!("{}", report.to_text(HISTOGRAM_WIDTH))
+    }
+    ExitCode::SUCCESS
 }
 
-fn print_aelves(slice : &[u32], filter_count : usize) -> () {
-  let mut total : u32 = 0;
-  for count in &slice[0..filter_count] {
-      total += count;
-      println!("{}", count);
-  }
-  println!("Total is {}", total);
+fn print_top(filter_count : usize) -> ExitCode {
+    let top = match read_top(io::stdin().lock(), filter_count) {
+        Ok(top) => top,
+        Err(error) => {
+            eprintln!("Invalid input at {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    for elf in top.to_sorted_vec() {
+        println!("{} (elf {})", elf.total, elf.index);
+    }
+    if !top.is_full() {
+        println!("Only {} elves", top.len());
+    }
+    println!("Total is {}", top.total());
+    let tie_count = top.boundary_tie_count();
+    if tie_count > 1 {
+        println!("{} elves are tied at the boundary", tie_count);
+    }
+    ExitCode::SUCCESS
 }
 
-fn main() {
-    let stdin        : io::Stdin = io::stdin();
-    let filter_count : usize     = get_filter_count();
-    let mut counters : Vec<u32>  = Vec::new();
-    sort_aelves(stdin, &mut counters);
-    print_aelves(&counters.as_slice(), filter_count);
+fn main() -> ExitCode {
+    let args : Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("report") => print_report(&args[1..]),
+        Some(count) => match count.parse::<usize>() {
+            Ok(count) => print_top(count),
+            Err(_) => {
+                eprintln!("Invalid filter count {}", count);
+                eprintln!("Usage: puzzle-01 [count={}] | puzzle-01 report [--percentiles 25,75] [--bins 10] [--format text|json]", DEFAULT_FILTER);
+                ExitCode::FAILURE
+            }
+        },
+        None => print_top(DEFAULT_FILTER)
+    }
 }
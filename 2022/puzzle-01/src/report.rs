@@ -0,0 +1,161 @@
+//! Statistics about the calories carried by elves.
+
+use serde::Serialize;
+
+/// A percentile of the totals.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Percentile {
+  /// Rank, from 0 to 100.
+  pub rank : f64,
+  /// Total at this rank, interpolated between the nearest totals.
+  pub value : f64
+}
+
+/// A bar of the histogram, counting the totals from `min` to `max` included.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Bin {
+  /// Lower bound.
+  pub min : u32,
+  /// Upper bound.
+  pub max : u32,
+  /// Count of totals within bounds.
+  pub count : usize
+}
+
+/// Distribution of the calories carried by elves.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+  /// Count of elves.
+  pub count : usize,
+  /// Lowest total.
+  pub min : u32,
+  /// Greatest total.
+  pub max : u32,
+  /// Mean of totals.
+  pub mean : f64,
+  /// Median of totals.
+  pub median : f64,
+  /// Requested percentiles.
+  pub percentiles : Vec<Percentile>,
+  /// Histogram of totals, lowest totals first.
+  pub histogram : Vec<Bin>
+}
+
+/// Returns the percentile of sorted values, by linear interpolation between
+/// the nearest ranks.
+pub fn percentile(sorted : &[u32], rank : f64) -> f64 {
+  assert!(!sorted.is_empty());
+  let position = rank.clamp(0.0, 100.0) / 100.0 * (sorted.len() - 1) as f64;
+  let lower = position.floor() as usize;
+  let upper = position.ceil() as usize;
+  let weight = position - lower as f64;
+  sorted[lower] as f64 * (1.0 - weight) + sorted[upper] as f64 * weight
+}
+
+/// Splits the range of sorted values into at most `bin_count` bins of the
+/// same width, and counts the values of each bin.
+pub fn histogram(sorted : &[u32], bin_count : usize) -> Vec<Bin> {
+  let (Some(min), Some(max)) = (sorted.first(), sorted.last()) else { return Vec::new() };
+  // Bounds are computed in u64, as they may exceed u32::MAX
+  let (min, max) = (*min as u64, *max as u64);
+  let range = max - min + 1;
+  let bin_count = (bin_count as u64).clamp(1, range);
+  let width = range.div_ceil(bin_count);
+  let mut bins : Vec<Bin> = (0..bin_count)
+    .map(|i| (min + i * width, (min + (i + 1) * width - 1).min(max)))
+    .filter(|(bin_min, _)| *bin_min <= max)
+    .map(|(bin_min, bin_max)| Bin { min: bin_min as u32, max: bin_max as u32, count: 0 })
+    .collect();
+  for value in sorted {
+    bins[((*value as u64 - min) / width) as usize].count += 1;
+  }
+  bins
+}
+
+/// Computes the report of the given totals, or `None` if there is none.
+pub fn report(totals : &[u32], ranks : &[f64], bin_count : usize) -> Option<Report> {
+  if totals.is_empty() {
+    return None;
+  }
+  let mut sorted = totals.to_vec();
+  sorted.sort_unstable();
+  Some(Report {
+    count: sorted.len(),
+    min: sorted[0],
+    max: sorted[sorted.len() - 1],
+    mean: sorted.iter().map(|total| *total as f64).sum::<f64>() / sorted.len() as f64,
+    median: percentile(&sorted, 50.0),
+    percentiles: ranks.iter().map(|rank| Percentile { rank: *rank, value: percentile(&sorted, *rank) }).collect(),
+    histogram: histogram(&sorted, bin_count)
+  })
+}
+
+impl Report {
+
+  /// Formats the report as text, with histogram bars of at most `width` characters.
+  pub fn to_text(&self, width : usize) -> String {
+    let mut lines : Vec<String> = vec![
+      format!("Count:  {}", self.count),
+      format!("Min:    {}", self.min),
+      format!("Max:    {}", self.max),
+      format!("Mean:   {:.2}", self.mean),
+      format!("Median: {:.1}", self.median),
+    ];
+    for p in &self.percentiles {
+      lines.push(format!("P{}: {:.1}", p.rank, p.value));
+    }
+    let greatest = self.histogram.iter().map(|bin| bin.count).max().unwrap_or(0).max(1);
+    let label_width = self.max.to_string().len();
+    for bin in &self.histogram {
+      let bar = "#".repeat(bin.count * width / greatest);
+      lines.push(format!("{:>w$}-{:>w$} | {:<width$} {}", bin.min, bin.max, bar, bin.count, w = label_width));
+    }
+    lines.join("\n")
+  }
+
+  /// Formats the report as JSON.
+  pub fn to_json(&self) -> String {
+    serde_json::to_string_pretty(self).expect("Serializable report")
+  }
+}
+
+#[test]
+fn test_report() {
+  let report = report(&[6000, 4000, 11000, 24000, 10000], &[25.0, 90.0], 2).expect("Not empty");
+  assert_eq!(5, report.count);
+  assert_eq!((4000, 24000), (report.min, report.max));
+  assert_eq!(11000.0, report.mean);
+  assert_eq!(10000.0, report.median);
+  assert_eq!(vec![6000.0, 18800.0], report.percentiles.iter().map(|p| p.value).collect::<Vec<f64>>());
+  assert_eq!(vec![
+    Bin { min: 4000, max: 14000, count: 4 },
+    Bin { min: 14001, max: 24000, count: 1 },
+  ], report.histogram);
+}
+
+#[test]
+fn test_histogram_narrow_range() {
+  assert_eq!(vec![
+    Bin { min: 3, max: 3, count: 2 },
+    Bin { min: 4, max: 4, count: 1 },
+  ], histogram(&[3, 3, 4], 10));
+  assert!(histogram(&[], 10).is_empty());
+}
+
+#[test]
+fn test_histogram_full_range() {
+  assert_eq!(vec![
+    Bin { min: 0, max: 1431655765, count: 1 },
+    Bin { min: 1431655766, max: 2863311531, count: 0 },
+    Bin { min: 2863311532, max: u32::MAX, count: 1 },
+  ], histogram(&[0, u32::MAX], 3));
+  assert_eq!(vec![Bin { min: 0, max: u32::MAX, count: 2 }], histogram(&[0, u32::MAX], 1));
+}
+
+#[test]
+fn test_json() {
+  let json = report(&[1, 2], &[], 1).expect("Not empty").to_json();
+  let value : serde_json::Value = serde_json::from_str(&json).expect("Valid JSON");
+  assert_eq!(2, value["count"]);
+  assert_eq!(1.5, value["median"]);
+}
@@ -0,0 +1,279 @@
+//! Streaming selection of the elves carrying the most calories.
+//!
+//! Totals are read one elf at a time and only the best ones are kept, so that
+//! inputs of any size are handled in `O(n log k)` time and `O(k)` memory.
+//! Rejected elves carrying as much as the lowest kept one are only counted,
+//! unless the selection is created by [`TopN::with_ties`].
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::io::BufRead;
+
+use aoc_common::ParseError;
+
+/// The calories carried by an elf.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElfTotal {
+  /// Position of the elf in the input, starting at 1.
+  pub index : usize,
+  /// Calories carried by the elf.
+  pub total : u32
+}
+
+/// Greater is better: more calories first, then the earliest elf.
+impl Ord for ElfTotal {
+  fn cmp(&self, other : &Self) -> Ordering {
+    self.total.cmp(&other.total)
+      .then_with(|| other.index.cmp(&self.index))
+  }
+}
+
+impl PartialOrd for ElfTotal {
+  fn partial_cmp(&self, other : &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+/// Reads the total of each elf from a reader, one elf at a time.
+///
+/// Elves are separated by blank lines.
+pub struct ElfTotals<R : BufRead> {
+  reader : R,
+  line : String,
+  line_number : usize,
+  index : usize
+}
+
+impl<R : BufRead> ElfTotals<R> {
+
+  /// Creates the iterator over the elves of the reader.
+  pub fn new(reader : R) -> ElfTotals<R> {
+    ElfTotals { reader, line: String::new(), line_number: 0, index: 0 }
+  }
+}
+
+impl<R : BufRead> Iterator for ElfTotals<R> {
+  type Item = Result<ElfTotal, ParseError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let mut total : Option<u32> = None;
+    loop {
+      self.line.clear();
+      let bytes_count = match self.reader.read_line(&mut self.line) {
+        Ok(bytes_count) => bytes_count,
+        Err(error) => return Some(Err(ParseError::new(self.line_number + 1, 1, "", &error.to_string())))
+      };
+      if bytes_count == 0 {
+        break; // EOF
+      }
+      self.line_number += 1;
+      let value_str = self.line.trim_end();
+      if value_str.is_empty() {
+        if total.is_some() {
+          break; // End of the elf
+        }
+      }
+      else {
+        match value_str.parse::<u32>() {
+          Ok(value) => match total.unwrap_or(0).checked_add(value) {
+            Some(sum) => total = Some(sum),
+            None => return Some(Err(ParseError::new(self.line_number, 1, value_str, "Calories overflow")))
+          },
+          Err(_) => return Some(Err(ParseError::new(self.line_number, 1, value_str, "Invalid calories")))
+        }
+      }
+    }
+    total.map(|total| {
+      self.index += 1;
+      Ok(ElfTotal { index: self.index, total })
+    })
+  }
+}
+
+/// Keeps the `capacity` best elves pushed so far.
+#[derive(Debug)]
+pub struct TopN {
+  capacity : usize,
+  // Lowest kept elf on top
+  heap : BinaryHeap<Reverse<ElfTotal>>,
+  // Count of rejected elves carrying as much as the lowest kept elf
+  tie_count : usize,
+  // The same elves, if asked for
+  ties : Option<Vec<ElfTotal>>
+}
+
+impl TopN {
+
+  /// Creates an empty selection of at most `capacity` elves.
+  pub fn new(capacity : usize) -> TopN {
+    TopN { capacity, heap: BinaryHeap::with_capacity(capacity), tie_count: 0, ties: None }
+  }
+
+  /// Creates an empty selection of at most `capacity` elves, also remembering
+  /// the rejected elves reported by [`TopN::boundary_ties`].
+  ///
+  /// Their count is not bounded: every elf is remembered if all carry the
+  /// same calories.
+  pub fn with_ties(capacity : usize) -> TopN {
+    TopN { ties: Some(Vec::new()), ..TopN::new(capacity) }
+  }
+
+  fn push_tie(&mut self, elf : ElfTotal) {
+    self.tie_count += 1;
+    if let Some(ties) = &mut self.ties {
+      ties.push(elf);
+    }
+  }
+
+  /// Returns the count of kept elves, which is lower than the capacity only
+  /// if fewer elves were pushed.
+  pub fn len(&self) -> usize {
+    self.heap.len()
+  }
+
+  /// Returns `true` if no elf is kept.
+  pub fn is_empty(&self) -> bool {
+    self.heap.is_empty()
+  }
+
+  /// Returns `true` if as many elves as the capacity are kept.
+  pub fn is_full(&self) -> bool {
+    self.heap.len() == self.capacity
+  }
+
+  /// Offers an elf to the selection.
+  pub fn push(&mut self, elf : ElfTotal) {
+    if self.capacity == 0 {
+      return;
+    }
+    if self.heap.len() < self.capacity {
+      self.heap.push(Reverse(elf));
+      return;
+    }
+    let Reverse(lowest) = *self.heap.peek().expect("Not empty");
+    if elf > lowest {
+      self.heap.pop();
+      self.heap.push(Reverse(elf));
+      let Reverse(new_lowest) = *self.heap.peek().expect("Not empty");
+      if lowest.total == new_lowest.total {
+        self.push_tie(lowest);
+      }
+      else {
+        self.tie_count = 0;
+        if let Some(ties) = &mut self.ties {
+          ties.clear();
+        }
+      }
+    }
+    else if elf.total == lowest.total {
+      self.push_tie(elf);
+    }
+  }
+
+  /// Returns the kept elves, the best first.
+  pub fn to_sorted_vec(&self) -> Vec<ElfTotal> {
+    let mut elves : Vec<ElfTotal> = self.heap.iter().map(|Reverse(elf)| *elf).collect();
+    elves.sort_by(|l, r| r.cmp(l));
+    elves
+  }
+
+  /// Returns the sum of calories of the kept elves, in u64 as it may exceed
+  /// u32::MAX.
+  pub fn total(&self) -> u64 {
+    self.heap.iter().map(|Reverse(elf)| elf.total as u64).sum()
+  }
+
+  /// Returns the kept elves carrying as much as the lowest kept elf.
+  fn lowest_kept(&self) -> impl Iterator<Item = ElfTotal> + '_ {
+    let lowest = self.heap.peek().map(|Reverse(elf)| elf.total);
+    self.heap.iter()
+      .map(|Reverse(elf)| *elf)
+      .filter(move |elf| Some(elf.total) == lowest)
+  }
+
+  /// Returns the count of elves carrying as much as the lowest kept elf,
+  /// kept or not.
+  ///
+  /// Returns 0 if the selection is not full.
+  pub fn boundary_tie_count(&self) -> usize {
+    if !self.is_full() || self.is_empty() {
+      return 0;
+    }
+    self.lowest_kept().count() + self.tie_count
+  }
+
+  /// Returns the elves carrying as much as the lowest kept elf, kept or not,
+  /// in input order. Rejected elves are only returned by a selection created
+  /// by [`TopN::with_ties`].
+  ///
+  /// Returns nothing if the selection is not full.
+  pub fn boundary_ties(&self) -> Vec<ElfTotal> {
+    if !self.is_full() || self.is_empty() {
+      return Vec::new();
+    }
+    let mut ties : Vec<ElfTotal> = self.lowest_kept()
+      .chain(self.ties.iter().flatten().copied())
+      .collect();
+    ties.sort_by_key(|elf| elf.index);
+    ties
+  }
+}
+
+impl Extend<ElfTotal> for TopN {
+  fn extend<T : IntoIterator<Item = ElfTotal>>(&mut self, iter : T) {
+    for elf in iter {
+      self.push(elf);
+    }
+  }
+}
+
+/// Reads all elves of the reader and keeps the `filter_count` best ones.
+pub fn read_top(reader : impl BufRead, filter_count : usize) -> Result<TopN, ParseError> {
+  let mut top = TopN::new(filter_count);
+  for elf in ElfTotals::new(reader) {
+    top.push(elf?);
+  }
+  Ok(top)
+}
+
+#[test]
+fn test_read_top() {
+  let top = read_top("5\n\n3\n\n1\n2\n\n3\n\n\n1".as_bytes(), 2).expect("Valid input");
+  assert_eq!(vec![ElfTotal { index: 1, total: 5 }, ElfTotal { index: 2, total: 3 }], top.to_sorted_vec());
+  assert_eq!(8, top.total());
+  assert_eq!(3, top.boundary_tie_count());
+  assert_eq!(vec![2], top.boundary_ties().iter().map(|elf| elf.index).collect::<Vec<usize>>());
+}
+
+#[test]
+fn test_unreadable_line() {
+  let error = read_top(&b"1\n\xff\n"[..], 3).expect_err("Invalid UTF-8");
+  assert_eq!((2, 1), (error.line, error.column));
+}
+
+#[test]
+fn test_calories_overflow() {
+  let error = read_top("1\n\n4000000000\n300000000\n".as_bytes(), 1).expect_err("Overflow");
+  assert_eq!((4, "Calories overflow"), (error.line, error.message.as_str()));
+  let top = read_top("4000000000\n\n300000000\n".as_bytes(), 2).expect("Valid input");
+  assert_eq!(4300000000, top.total());
+}
+
+#[test]
+fn test_more_than_elves() {
+  let top = read_top("5\n\n3\n".as_bytes(), 4).expect("Valid input");
+  assert_eq!(2, top.len());
+  assert!(!top.is_full());
+  assert_eq!(8, top.total());
+  assert!(top.boundary_ties().is_empty());
+  assert_eq!(0, top.boundary_tie_count());
+}
+
+#[test]
+fn test_evicted_ties() {
+  let mut top = TopN::with_ties(1);
+  top.extend([2, 2, 3, 3, 1].iter().enumerate().map(|(i, total)| ElfTotal { index: i + 1, total: *total }));
+  assert_eq!(vec![ElfTotal { index: 3, total: 3 }], top.to_sorted_vec());
+  assert_eq!(vec![3, 4], top.boundary_ties().iter().map(|elf| elf.index).collect::<Vec<usize>>());
+  assert_eq!(2, top.boundary_tie_count());
+}
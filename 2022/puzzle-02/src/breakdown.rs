@@ -0,0 +1,115 @@
+//! Round by round scores of the strategy guide, for both reading modes.
+
+use std::cmp::Ordering;
+
+use serde::Serialize;
+
+use crate::game::outcome_score;
+use crate::{resolve_round, result_mapping, result_name, shape_scores, ReadingMode, Shape, StrategyGuide};
+
+/// Score of a round according to one reading mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct RoundScore {
+    /// Shape played.
+    pub player: Shape,
+    /// Result of the round: `lose`, `draw` or `win`.
+    pub outcome: &'static str,
+    /// Score of the shape played.
+    pub shape_score: i32,
+    /// Score of the result.
+    pub outcome_score: i32,
+    /// Total score of the guide up to this round included.
+    pub running_total: i32
+}
+
+/// Scores of a round according to both reading modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct RoundBreakdown {
+    /// Round number, from 1.
+    pub round: usize,
+    /// Shape of the opponent.
+    pub opponent: Shape,
+    /// Score with the [`ReadingMode::Shape`] mode.
+    pub shape: RoundScore,
+    /// Score with the [`ReadingMode::PlayResult`] mode.
+    pub play_result: RoundScore
+}
+
+/// Scores every round of the guide with both reading modes, in a single pass.
+pub fn breakdown(guide: &StrategyGuide) -> impl Iterator<Item = RoundBreakdown> + '_ {
+    let shape_scores = shape_scores();
+    let result_mapping = result_mapping();
+    let mut totals = (0, 0);
+    guide.rounds.iter().enumerate().map(move |(index, (opponent, player))| {
+        let score = |mode: &ReadingMode, running_total: &mut i32| {
+            let (player, result) : (Shape, Ordering) = resolve_round(&result_mapping, opponent, player, mode);
+            let shape_score = shape_scores[&player];
+            let outcome_score = outcome_score(result);
+            *running_total += shape_score + outcome_score;
+            RoundScore {
+                player,
+                outcome: result_name(result),
+                shape_score,
+                outcome_score,
+                running_total: *running_total
+            }
+        };
+        RoundBreakdown {
+            round: index + 1,
+            opponent: *opponent,
+            shape: score(&ReadingMode::Shape, &mut totals.0),
+            play_result: score(&ReadingMode::PlayResult, &mut totals.1)
+        }
+    })
+}
+
+/// Column names of [`to_csv`].
+pub const CSV_HEADER: &str = "round,opponent,\
+    shape_player,shape_outcome,shape_shape_score,shape_outcome_score,shape_running_total,\
+    result_player,result_outcome,result_shape_score,result_outcome_score,result_running_total";
+
+/// Returns the rounds as CSV, with a [`CSV_HEADER`] line.
+pub fn to_csv(rounds: impl Iterator<Item = RoundBreakdown>) -> String {
+    let columns = |score: &RoundScore| format!("{:?},{},{},{},{}",
+        score.player, score.outcome, score.shape_score, score.outcome_score, score.running_total);
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+    for round in rounds {
+        csv.push_str(&format!("{},{:?},{},{}\n",
+            round.round, round.opponent, columns(&round.shape), columns(&round.play_result)));
+    }
+    csv
+}
+
+/// Returns the rounds as a JSON array.
+pub fn to_json(rounds: impl Iterator<Item = RoundBreakdown>) -> String {
+    serde_json::to_string_pretty(&rounds.collect::<Vec<_>>()).expect("Serializable rounds")
+}
+
+#[test]
+fn test_breakdown() {
+    use aoc_common::Solution;
+    let guide = StrategyGuide::parse("A Y\nB X\nC Z\n").expect("Valid input");
+    let rounds : Vec<RoundBreakdown> = breakdown(&guide).collect();
+    assert_eq!(3, rounds.len());
+    assert_eq!(
+        RoundScore { player: Shape::Paper, outcome: "win", shape_score: 2, outcome_score: 6, running_total: 8 },
+        rounds[0].shape);
+    assert_eq!(
+        RoundScore { player: Shape::Rock, outcome: "draw", shape_score: 1, outcome_score: 3, running_total: 4 },
+        rounds[0].play_result);
+    assert_eq!((15, 12), (rounds[2].shape.running_total, rounds[2].play_result.running_total));
+}
+
+#[test]
+fn test_formats() {
+    use aoc_common::Solution;
+    let guide = StrategyGuide::parse("A Y\n").expect("Valid input");
+    assert_eq!(
+        format!("{}\n1,Rock,Paper,win,2,6,8,Rock,draw,1,3,4\n", CSV_HEADER),
+        to_csv(breakdown(&guide)));
+    let value : serde_json::Value = serde_json::from_str(&to_json(breakdown(&guide))).expect("Valid JSON");
+    assert_eq!("Rock", value[0]["opponent"]);
+    assert_eq!("draw", value[0]["play_result"]["outcome"]);
+    assert_eq!(8, value[0]["shape"]["running_total"]);
+}
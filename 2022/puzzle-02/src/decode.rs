@@ -0,0 +1,130 @@
+//! Decoding of the second column of the strategy guide.
+//!
+//! The meaning of `X`, `Y` and `Z` is not known for sure: each bijection onto
+//! the shapes, or onto the round results, is scored and ranked.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::{get_score, play, result_name, shape_scores, solve_shape, Shape, StrategyGuide};
+
+/// Letters of the second column, in the order of the [`Shape::ALL`] they are
+/// parsed into.
+pub const LETTERS: [char; 3] = ['X', 'Y', 'Z'];
+
+/// Meaning given to each letter of the second column, in [`LETTERS`] order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpretation {
+    /// Each letter is the shape to be played.
+    Shapes([Shape; 3]),
+    /// Each letter is the expected result of the round.
+    Results([Ordering; 3])
+}
+
+impl fmt::Display for Interpretation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let meanings : Vec<String> = match self {
+            Interpretation::Shapes(shapes) => shapes.iter()
+                .map(|shape| format!("{:?}", shape))
+                .collect(),
+            Interpretation::Results(results) => results.iter()
+                .map(|result| String::from(result_name(*result)))
+                .collect()
+        };
+        let pairs : Vec<String> = LETTERS.iter().zip(meanings)
+            .map(|(letter, meaning)| format!("{}={}", letter, meaning))
+            .collect();
+        write!(f, "{}", pairs.join(" "))
+    }
+}
+
+/// Returns the 6 orderings of `items`.
+pub fn permutations<T: Copy>(items: [T; 3]) -> Vec<[T; 3]> {
+    let [a, b, c] = items;
+    vec![[a, b, c], [a, c, b], [b, a, c], [b, c, a], [c, a, b], [c, b, a]]
+}
+
+/// Returns every interpretation: first the shape ones, then the result ones.
+pub fn interpretations() -> Vec<Interpretation> {
+    let shapes = permutations(Shape::ALL).into_iter()
+        .map(Interpretation::Shapes);
+    let results = permutations([Ordering::Less, Ordering::Equal, Ordering::Greater]).into_iter()
+        .map(Interpretation::Results);
+    shapes.chain(results).collect()
+}
+
+/// Total score of the guide under an interpretation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ranked {
+    /// Meaning given to the second column.
+    pub interpretation: Interpretation,
+    /// Total score of all rounds.
+    pub total: i32
+}
+
+/// Counts the rounds of the guide by (opponent, letter) index.
+fn round_counts(guide: &StrategyGuide) -> [[i32; 3]; 3] {
+    let mut counts = [[0; 3]; 3];
+    for (opponent, player) in &guide.rounds {
+        counts[opponent.index()][player.index()] += 1;
+    }
+    counts
+}
+
+/// Returns the total score of the guide under `interpretation`.
+pub fn total_score(guide: &StrategyGuide, interpretation: &Interpretation) -> i32 {
+    let score_map = shape_scores();
+    let counts = round_counts(guide);
+    let mut total = 0;
+    for opponent in Shape::ALL {
+        for letter in 0..LETTERS.len() {
+            let (player, result) = match interpretation {
+                Interpretation::Shapes(shapes) =>
+                    (shapes[letter], play(&shapes[letter], &opponent)),
+                Interpretation::Results(results) =>
+                    (solve_shape(&opponent, results[letter]), results[letter])
+            };
+            total += counts[opponent.index()][letter] * get_score(&score_map, &player, result);
+        }
+    }
+    total
+}
+
+/// Returns every interpretation with its total score, best first. Equal
+/// totals keep the [`interpretations`] order.
+pub fn rank(guide: &StrategyGuide) -> Vec<Ranked> {
+    let mut ranking : Vec<Ranked> = interpretations().into_iter()
+        .map(|interpretation| Ranked {
+            interpretation,
+            total: total_score(guide, &interpretation)
+        })
+        .collect();
+    ranking.sort_by_key(|ranked| -ranked.total);
+    ranking
+}
+
+#[test]
+fn test_default_interpretations() {
+    use aoc_common::Solution;
+    let guide = StrategyGuide::parse("A Y\nB X\nC Z\n").expect("Valid input");
+    let shapes = Interpretation::Shapes(Shape::ALL);
+    let results = Interpretation::Results([Ordering::Less, Ordering::Equal, Ordering::Greater]);
+    assert_eq!(guide.part1(), total_score(&guide, &shapes));
+    assert_eq!(guide.part2(), total_score(&guide, &results));
+    assert_eq!("X=Rock Y=Paper Z=Scissor", shapes.to_string());
+    assert_eq!("X=lose Y=draw Z=win", results.to_string());
+}
+
+#[test]
+fn test_rank() {
+    use aoc_common::Solution;
+    let guide = StrategyGuide::parse("A Y\nB X\nC Z\n").expect("Valid input");
+    let ranking = rank(&guide);
+    assert_eq!(12, ranking.len());
+    assert!(ranking.windows(2).all(|pair| pair[0].total >= pair[1].total));
+    // Winning every round: 6 * 3 plus paper, scissor and rock.
+    assert_eq!(24, ranking[0].total);
+    assert_eq!(
+        Interpretation::Shapes([Shape::Scissor, Shape::Paper, Shape::Rock]),
+        ranking[0].interpretation);
+}
@@ -0,0 +1,124 @@
+//! Cyclic-dominance games generalising rock paper scissors.
+//!
+//! Shapes are numbered `0..N` with `N` odd, and each shape defeats the
+//! `(N - 1) / 2` shapes preceding it (cyclically). Rock paper scissors is the
+//! game `[Rock, Paper, Scissor]`; rock paper scissors lizard Spock is the game
+//! `[Rock, Spock, Paper, Lizard, Scissors]`.
+
+use std::cmp::{Ordering, Reverse};
+
+/// Returns the result of a round from the `player` point of view, in a game
+/// of `shape_count` shapes.
+pub fn outcome(shape_count: usize, player: usize, opponent: usize) -> Ordering {
+    let distance = (player + shape_count - opponent) % shape_count;
+    if distance == 0 {
+        Ordering::Equal
+    } else if distance <= (shape_count - 1) / 2 {
+        Ordering::Greater
+    } else {
+        Ordering::Less
+    }
+}
+
+/// Returns the shapes to be played against `opponent` in order to get
+/// `result`, in a game of `shape_count` shapes.
+pub fn candidates(shape_count: usize, opponent: usize, result: Ordering) -> impl Iterator<Item = usize> {
+    (0..shape_count).filter(move |player| outcome(shape_count, *player, opponent) == result)
+}
+
+/// Returns the score of a result: 0 for a loss, 3 for a draw and 6 for a win.
+pub fn outcome_score(result: Ordering) -> i32 {
+    match result {
+        Ordering::Less => 0,
+        Ordering::Equal => 3,
+        Ordering::Greater => 6
+    }
+}
+
+/// Rule table of a cyclic-dominance game: the score of each shape, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    scores: Vec<i32>
+}
+
+impl Game {
+
+    /// Creates a game with one shape per score. The shape count must be odd
+    /// so that every shape defeats as many shapes as it loses against.
+    pub fn new(scores: Vec<i32>) -> Result<Game, &'static str> {
+        if scores.is_empty() {
+            Err("No shape")
+        } else if scores.len().is_multiple_of(2) {
+            Err("Even shape count")
+        } else {
+            Ok(Game { scores })
+        }
+    }
+
+    /// Rock (1), paper (2) and scissor (3).
+    pub fn classic() -> Game {
+        Game { scores: vec![1, 2, 3] }
+    }
+
+    /// Returns the number of shapes.
+    pub fn shape_count(&self) -> usize {
+        self.scores.len()
+    }
+
+    /// Returns the score of playing `shape`.
+    pub fn shape_score(&self, shape: usize) -> i32 {
+        self.scores[shape]
+    }
+
+    /// Returns the result of a round from the `player` point of view.
+    pub fn play(&self, player: usize, opponent: usize) -> Ordering {
+        outcome(self.shape_count(), player, opponent)
+    }
+
+    /// Returns the best scoring shape to be played against `opponent` in
+    /// order to get `result`. Ties are won by the first shape.
+    pub fn solve_shape(&self, opponent: usize, result: Ordering) -> usize {
+        candidates(self.shape_count(), opponent, result)
+            .min_by_key(|shape| Reverse(self.scores[*shape]))
+            .expect("Every result is reachable")
+    }
+
+    /// Returns the score of a round: the score of the player shape plus the
+    /// score of the result.
+    pub fn score(&self, player: usize, result: Ordering) -> i32 {
+        self.shape_score(player) + outcome_score(result)
+    }
+}
+
+#[test]
+fn test_classic() {
+    let game = Game::classic();
+    assert_eq!(Ordering::Greater, game.play(0, 2));
+    assert_eq!(Ordering::Greater, game.play(1, 0));
+    assert_eq!(Ordering::Less, game.play(2, 0));
+    assert_eq!(Ordering::Equal, game.play(1, 1));
+    assert_eq!(2, game.solve_shape(1, Ordering::Greater));
+    assert_eq!(8, game.score(1, Ordering::Greater));
+}
+
+#[test]
+fn test_rock_paper_scissors_lizard_spock() {
+    let [rock, spock, paper, lizard, scissors] = [0, 1, 2, 3, 4];
+    let game = Game::new(vec![1, 5, 2, 4, 3]).expect("Valid game");
+    let wins = [(rock, scissors), (rock, lizard), (paper, rock), (paper, spock),
+        (scissors, paper), (scissors, lizard), (lizard, spock), (lizard, paper),
+        (spock, scissors), (spock, rock)];
+    for (winner, loser) in wins {
+        assert_eq!(Ordering::Greater, game.play(winner, loser));
+        assert_eq!(Ordering::Less, game.play(loser, winner));
+    }
+    // Both paper and spock defeat rock, spock scores more.
+    assert_eq!(vec![spock, paper], candidates(5, rock, Ordering::Greater).collect::<Vec<_>>());
+    assert_eq!(spock, game.solve_shape(rock, Ordering::Greater));
+}
+
+#[test]
+fn test_invalid_game() {
+    assert_eq!(Err("No shape"), Game::new(vec![]));
+    assert_eq!(Err("Even shape count"), Game::new(vec![1, 2, 3, 4]));
+}
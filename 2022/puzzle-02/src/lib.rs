@@ -0,0 +1,226 @@
+//! Day 2: Rock Paper Scissors
+//!
+//! Computes the total score of a strategy guide.
+
+#![warn(missing_docs)]
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use aoc_common::{ParseError, Solution};
+use serde::Serialize;
+
+pub mod breakdown;
+pub mod decode;
+pub mod game;
+pub mod tournament;
+
+/// A shape played by hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum Shape {
+    /// Defeats scissors.
+    Rock,
+    /// Defeats rock.
+    Paper,
+    /// Defeats paper.
+    Scissor
+}
+
+impl Shape {
+    /// All shapes, in [`game`] rule table order: each defeats the previous one.
+    pub const ALL: [Shape; 3] = [Shape::Rock, Shape::Paper, Shape::Scissor];
+
+    /// Returns the position of the shape in [`Shape::ALL`].
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// Returns the shape at `index` in [`Shape::ALL`].
+    pub fn from_index(index: usize) -> Shape {
+        Shape::ALL[index]
+    }
+}
+
+/// Returns the result of a round from the `player` point of view.
+pub fn play(player : &Shape, opponent : &Shape) -> Ordering {
+    game::outcome(Shape::ALL.len(), player.index(), opponent.index())
+}
+
+/// Returns the score of a round: the score of the player shape plus the
+/// score of the result (0 for a loss, 3 for a draw and 6 for a win).
+pub fn get_score(
+        score_map: &HashMap<Shape, i32>,
+        player : &Shape,
+        play_result : Ordering) -> i32 {
+    let shape_score = *(score_map.get(player).expect("Unknown shape"));
+    shape_score + game::outcome_score(play_result)
+}
+
+/// Returns the shape to be played against `opponent` in order to get `result`.
+pub fn solve_shape(opponent : &Shape, result: Ordering) -> Shape {
+    let index = game::candidates(Shape::ALL.len(), opponent.index(), result)
+        .next()
+        .expect("Every result is reachable");
+    Shape::from_index(index)
+}
+
+/// Parses a guide line into the (opponent, player) shapes, using the
+/// character mapping of `mapper`.
+pub fn parse_line<'a>(mapper: &'a HashMap<u8, Shape>, line_number: usize, line: &str) -> Result<(&'a Shape, &'a Shape), ParseError> {
+    if line.len() < 3 {
+        return Err(ParseError::new(line_number, 1, line, "Incomplete round"));
+    }
+    let shape_at = |index: usize| {
+        let token = line.get(index..index + 1);
+        token.and_then(|t| mapper.get(&t.as_bytes()[0]))
+            .ok_or_else(|| {
+                let text : String = line.get(index..).and_then(|s| s.chars().next()).map(String::from).unwrap_or_default();
+                ParseError::new(line_number, index + 1, &text, "Unknown shape")
+            })
+    };
+    let opponent_shape = shape_at(0)?;
+    let player_shape = shape_at(2)?;
+    Ok((opponent_shape, player_shape))
+}
+
+/// Returns the name of a result: `lose`, `draw` or `win`.
+pub fn result_name(result: Ordering) -> &'static str {
+    match result {
+        Ordering::Less => "lose",
+        Ordering::Equal => "draw",
+        Ordering::Greater => "win"
+    }
+}
+
+/// Meaning of the second column of the strategy guide.
+pub enum ReadingMode {
+    /// The shape to be played.
+    Shape,
+    /// The expected result of the round.
+    PlayResult
+}
+
+/// Returns the shape played and the result of a round, according to the
+/// reading mode.
+pub fn resolve_round(
+    result_map: &HashMap<Shape, Ordering>,
+    opponent: &Shape,
+    player: &Shape,
+    mode: &ReadingMode) -> (Shape, Ordering) {
+
+    let play_result : Ordering = match mode {
+        ReadingMode::Shape => {
+            play(player, opponent)
+        },
+        ReadingMode::PlayResult => {
+            *(result_map.get(player).expect("Result not found"))
+        }
+    };
+
+    let player_shape = match mode {
+        ReadingMode::Shape => {
+            *player
+        },
+        ReadingMode::PlayResult => {
+            solve_shape(opponent, play_result)
+        }
+    };
+
+    (player_shape, play_result)
+}
+
+/// Returns the score of each shape.
+pub fn shape_scores() -> HashMap<Shape, i32> {
+    let game = game::Game::classic();
+    Shape::ALL.iter()
+        .map(|shape| (*shape, game.shape_score(shape.index())))
+        .collect()
+}
+
+/// Returns the result given by each shape, for the [`ReadingMode::PlayResult`] mode.
+pub fn result_mapping() -> HashMap<Shape, Ordering> {
+    HashMap::from([
+        (Shape::Rock, Ordering::Less),
+        (Shape::Paper, Ordering::Equal),
+        (Shape::Scissor, Ordering::Greater),
+    ])
+}
+
+/// Returns the shape of each guide character.
+pub fn parsing_mapping() -> HashMap<u8, Shape> {
+    HashMap::from([
+        ("A".as_bytes()[0], Shape::Rock),
+        ("B".as_bytes()[0], Shape::Paper),
+        ("C".as_bytes()[0], Shape::Scissor),
+        ("X".as_bytes()[0], Shape::Rock),
+        ("Y".as_bytes()[0], Shape::Paper),
+        ("Z".as_bytes()[0], Shape::Scissor),
+    ])
+}
+
+/// Rounds of the strategy guide, as (opponent, player) shapes.
+#[derive(Debug)]
+pub struct StrategyGuide {
+    /// Shapes of each round, as (opponent, player).
+    pub rounds: Vec<(Shape, Shape)>
+}
+
+impl StrategyGuide {
+
+    /// Returns the total score of all rounds, according to the reading mode.
+    pub fn total_score(&self, mode: &ReadingMode) -> i32 {
+        let shape_scores = shape_scores();
+        let result_mapping = result_mapping();
+        self.rounds.iter()
+            .map(|(opponent, player)| resolve_round(&result_mapping, opponent, player, mode))
+            .map(|(player, play_result)| get_score(&shape_scores, &player, play_result))
+            .sum()
+    }
+
+    /// Returns the total scores of both reading modes, as
+    /// ([`ReadingMode::Shape`], [`ReadingMode::PlayResult`]), in a single pass.
+    pub fn total_scores(&self) -> (i32, i32) {
+        breakdown::breakdown(self).last()
+            .map_or((0, 0), |round| (round.shape.running_total, round.play_result.running_total))
+    }
+}
+
+impl Solution for StrategyGuide {
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let parsing_mapping = parsing_mapping();
+        let rounds = input.lines()
+            .enumerate()
+            .map(|(index, line)| parse_line(&parsing_mapping, index + 1, line))
+            .map(|result| result.map(|(opponent, player)| (*opponent, *player)))
+            .collect::<Result<Vec<(Shape, Shape)>, ParseError>>()?;
+        Ok(StrategyGuide { rounds })
+    }
+
+    fn part1(&self) -> i32 {
+        self.total_score(&ReadingMode::Shape)
+    }
+
+    fn part2(&self) -> i32 {
+        self.total_score(&ReadingMode::PlayResult)
+    }
+}
+
+#[test]
+fn test_example() {
+    let guide = StrategyGuide::parse("A Y\nB X\nC Z\n").expect("Valid input");
+    assert_eq!(15, guide.part1());
+    assert_eq!(12, guide.part2());
+    assert_eq!((15, 12), guide.total_scores());
+}
+
+#[test]
+fn test_unknown_shape() {
+    let error = StrategyGuide::parse("A Y\nB W\n").expect_err("Invalid input");
+    assert_eq!((2, 3), (error.line, error.column));
+    assert_eq!("W", error.text);
+    let error = StrategyGuide::parse("Aéx\n").expect_err("Invalid input");
+    assert_eq!(3, error.column);
+}
@@ -1,162 +1,148 @@
-
 use std::env;
-use std::cmp::Ordering;
-use std::io::{self, BufRead};
-use std::collections::HashMap;
-
-#[derive(Debug, PartialEq, Eq, Hash)]
-enum Shape {
-    Rock,
-    Paper,
-    Scissor
+use std::io::{self, Read};
+use std::process::ExitCode;
+
+use aoc_common::Solution;
+use puzzle_02::breakdown::{breakdown, to_csv, to_json};
+use puzzle_02::tournament::{FixedGuide, FrequencyCounter, MarkovPredictor, Random, Tournament};
+use puzzle_02::{decode, ReadingMode, StrategyGuide};
+
+static DEFAULT_ROUNDS : usize = 1000;
+static DEFAULT_SEED : u64 = 2022;
+
+/// Options of the tournament mode.
+struct TournamentArgs {
+    rounds : usize,
+    seed : u64,
+    log : bool
 }
 
-fn play(player : &Shape, opponent : &Shape, may_recurse : bool) -> Ordering {
-    if *player == *opponent {
-        Ordering::Equal
-    }
-    else if
-        (*player == Shape::Rock && *opponent == Shape::Scissor) ||
-        (*player == Shape::Scissor && *opponent == Shape::Paper) ||
-        (*player == Shape::Paper && *opponent == Shape::Rock) {
-        Ordering::Greater
-    }
-    else if may_recurse {
-        play(opponent, player, false).reverse()
-    }
-    else {
-        panic!("Invalid state {:?} {:?}", player, opponent);
+fn parse_tournament_args(args : &[String]) -> Result<TournamentArgs, String> {
+    let mut tournament_args = TournamentArgs { rounds: DEFAULT_ROUNDS, seed: DEFAULT_SEED, log: false };
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--rounds" => {
+                let rounds = value()?;
+                tournament_args.rounds = rounds.parse().map_err(|_| format!("Invalid round count {}", rounds))?;
+            },
+            "--seed" => {
+                let seed = value()?;
+                tournament_args.seed = seed.parse().map_err(|_| format!("Invalid seed {}", seed))?;
+            },
+            "--log" => tournament_args.log = true,
+            _ => return Err(format!("Unknown option {}", arg))
+        }
     }
+    Ok(tournament_args)
 }
 
-fn get_score(
-        score_map: &HashMap<Shape, i32>,
-        player : &Shape,
-        play_result : Ordering) -> i32 {
-    let shape_score = *(score_map.get(player).expect("Unknown shape"));
-    let play_score = match play_result {
-        Ordering::Less => 0,
-        Ordering::Equal => 3,
-        Ordering::Greater => 6
-        };
-    shape_score + play_score
+fn read_guide() -> Result<StrategyGuide, ExitCode> {
+    let mut input = String::new();
+    if let Err(error) = io::stdin().read_to_string(&mut input) {
+        eprintln!("Impossible to read input: {}", error);
+        return Err(ExitCode::FAILURE);
+    }
+    StrategyGuide::parse(&input).map_err(|error| {
+        eprintln!("Invalid input at {}", error);
+        ExitCode::FAILURE
+    })
 }
 
-fn solve_shape(opponent : &Shape, result: Ordering) -> &Shape {
-    match result {
-        Ordering::Equal => opponent,
-        Ordering::Less => {
-            if *opponent == Shape::Rock {
-                &Shape::Scissor
-            } else if *opponent == Shape::Paper {
-                &Shape::Rock
-            } else { // SCISSOR
-                &Shape::Paper
-            }
+fn print_tournament(args : &[String]) -> ExitCode {
+    let tournament_args = match parse_tournament_args(args) {
+        Ok(tournament_args) => tournament_args,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!("Usage: puzzle-02 tournament [--rounds 1000] [--seed 2022] [--log]");
+            return ExitCode::FAILURE;
+        }
+    };
+    let guide = match read_guide() {
+        Ok(guide) if guide.rounds.is_empty() => {
+            eprintln!("Empty guide");
+            return ExitCode::FAILURE;
         },
-        Ordering::Greater => {
-            if *opponent == Shape::Rock {
-                &Shape::Paper
-            } else if *opponent == Shape::Paper {
-                &Shape::Scissor
-            } else { // SCISSOR
-                &Shape::Rock
+        Ok(guide) => guide,
+        Err(code) => return code
+    };
+    let mut tournament = Tournament::new(tournament_args.rounds);
+    tournament
+        .add(Box::new(FixedGuide::new(guide.rounds.iter().map(|(_, player)| *player).collect())))
+        .add(Box::new(Random::new(tournament_args.seed)))
+        .add(Box::new(FrequencyCounter))
+        .add(Box::new(MarkovPredictor));
+    let names : Vec<String> = tournament.names();
+    let (matches, standings) = tournament.run();
+    if tournament_args.log {
+        for log in &matches {
+            println!("{} vs {}", names[log.players.0], names[log.players.1]);
+            for round in &log.rounds {
+                println!("{:6} {:?} {:?} {} {}", round.round,
+                    round.shapes.0, round.shapes.1, round.scores.0, round.scores.1);
             }
         }
     }
+    for (rank, standing) in standings.iter().enumerate() {
+        println!("{}. {} {} ({} wins, {} draws, {} losses)", rank + 1,
+            standing.name, standing.score, standing.wins, standing.draws, standing.losses);
+    }
+    ExitCode::SUCCESS
 }
 
-fn parse_line<'a>(mapper: &'a HashMap<u8, Shape>, line: &String) -> (&'a Shape, &'a Shape) {
-    assert!(line.len() >= 3);
-    let bytes = line.as_bytes();
-    let opponent = bytes[0];
-    assert!(opponent.is_ascii());
-    let opponent_shape = mapper.get(&opponent).unwrap();
-    let player = bytes[2];
-    assert!(player.is_ascii());
-    let player_shape = mapper.get(&player).unwrap();
-    (opponent_shape, player_shape)
-}
-
-enum ReadingMode {
-    Shape,
-    PlayResult
-}
-
-fn get_score_from_line(
-    char_map: &HashMap<u8, Shape>,
-    score_map: &HashMap<Shape, i32>,
-    result_map: &HashMap<Shape, Ordering>,
-    line: &String,
-    mode: &ReadingMode) -> i32 {
-
-    let (opponent, player) = parse_line(char_map, line);
-
-    let play_result : Ordering = match mode {
-        ReadingMode::Shape => {
-            play(&player, &opponent, true)
-        },
-        ReadingMode::PlayResult => {
-            *(result_map.get(&player).expect("Result not found"))
+fn print_both(args : &[String]) -> ExitCode {
+    let format = match args {
+        [] => None,
+        [option, format] if option == "--table" && (format == "csv" || format == "json") => Some(format.as_str()),
+        _ => {
+            eprintln!("Usage: puzzle-02 both [--table csv|json]");
+            return ExitCode::FAILURE;
         }
     };
-
-    let player_shape = match mode {
-        ReadingMode::Shape => {
-            player
-        },
-        ReadingMode::PlayResult => {
-            &(solve_shape(&opponent, play_result))
-        }
+    let guide = match read_guide() {
+        Ok(guide) => guide,
+        Err(code) => return code
     };
-
-    get_score(score_map, player_shape, play_result)
+    match format {
+        Some("csv") => print!("{}", to_csv(breakdown(&guide))),
+        Some(_) => println!("{}", to_json(breakdown(&guide))),
+        None => {
+            let (shape_total, result_total) = guide.total_scores();
+            println!("Shape total = {}", shape_total);
+            println!("Result total = {}", result_total);
+        }
+    }
+    ExitCode::SUCCESS
 }
 
-fn main() {
-
-    // Argument
-    let args: Vec<String> = env::args().collect();
-    let reading_mode : ReadingMode = if args.len() > 1
-        && ! String::from("shape").eq_ignore_ascii_case(args.get(1).unwrap())
-        { ReadingMode::PlayResult } else { ReadingMode::Shape };
+fn print_decoding() -> ExitCode {
+    let guide = match read_guide() {
+        Ok(guide) => guide,
+        Err(code) => return code
+    };
+    for (rank, ranked) in decode::rank(&guide).iter().enumerate() {
+        println!("{:2}. {:6} {}", rank + 1, ranked.total, ranked.interpretation);
+    }
+    ExitCode::SUCCESS
+}
 
-    // Init
-    let shape_scores = HashMap::from([
-        (Shape::Rock,    1),
-        (Shape::Paper,   2),
-        (Shape::Scissor, 3),
-    ]);
-    let result_mapping = HashMap::from([
-        (Shape::Rock, Ordering::Less),
-        (Shape::Paper, Ordering::Equal),
-        (Shape::Scissor, Ordering::Greater),
-    ]);
-    let parsing_mapping = HashMap::from([
-        ("A".as_bytes()[0], Shape::Rock),
-        ("B".as_bytes()[0], Shape::Paper),
-        ("C".as_bytes()[0], Shape::Scissor),
-        ("X".as_bytes()[0], Shape::Rock),
-        ("Y".as_bytes()[0], Shape::Paper),
-        ("Z".as_bytes()[0], Shape::Scissor),
-    ]);
+fn print_total(reading_mode : ReadingMode) -> ExitCode {
+    let guide = match read_guide() {
+        Ok(guide) => guide,
+        Err(code) => return code
+    };
+    println!("Total = {}", guide.total_score(&reading_mode));
+    ExitCode::SUCCESS
+}
 
-    // Reading
-    let stdin : io::Stdin = io::stdin();
-    let mut lines = stdin.lock().lines();
-    let mut total : i32 = 0;
-    loop {
-        if let Some(line) = lines.next() {
-          let str = line.expect("Impossible to read line");
-          let line_value : i32 = get_score_from_line(
-            &parsing_mapping, &shape_scores, &result_mapping,
-            &str, &reading_mode);
-          total += line_value;
-        } else {
-          break;
-        }
+fn main() -> ExitCode {
+    let args : Vec<String> = env::args().skip(1).collect();
+    match args.first().map(|arg| arg.to_ascii_lowercase()).as_deref() {
+        Some("tournament") => print_tournament(&args[1..]),
+        Some("decode") => print_decoding(),
+        Some("both") => print_both(&args[1..]),
+        Some("shape") | None => print_total(ReadingMode::Shape),
+        Some(_) => print_total(ReadingMode::PlayResult)
     }
-
-    // Printing
-    println!("Total = {}", total);
 }
@@ -0,0 +1,295 @@
+//! Round-robin tournament between player strategies.
+//!
+//! Every pair of players plays a match of a fixed number of rounds, scored
+//! like the strategy guide. The leaderboard sums the scores of all matches.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::{get_score, play, shape_scores, solve_shape, Shape};
+
+/// Chooses the shape of each round of a match.
+pub trait Strategy {
+    /// Returns the name displayed in the leaderboard.
+    fn name(&self) -> String;
+
+    /// Returns the shape of the next round. `history` holds the previous
+    /// rounds of the match, as (own, opponent) shapes.
+    fn choose(&mut self, history: &[(Shape, Shape)]) -> Shape;
+}
+
+/// Returns the shape defeating `shape`.
+fn counter(shape: &Shape) -> Shape {
+    solve_shape(shape, Ordering::Greater)
+}
+
+/// Returns the most frequent shape, the first one of [`Shape::ALL`] on ties.
+fn most_frequent<'a>(shapes: impl Iterator<Item = &'a Shape>) -> Option<Shape> {
+    let mut counts = [0usize; 3];
+    for shape in shapes {
+        counts[shape.index()] += 1;
+    }
+    Shape::ALL.iter()
+        .filter(|shape| counts[shape.index()] > 0)
+        .rev()
+        .max_by_key(|shape| counts[shape.index()])
+        .copied()
+}
+
+/// Plays the shapes of a strategy guide, over and over.
+pub struct FixedGuide {
+    shapes: Vec<Shape>
+}
+
+impl FixedGuide {
+    /// Creates a strategy playing `shapes` in order. `shapes` must not be empty.
+    pub fn new(shapes: Vec<Shape>) -> FixedGuide {
+        assert!(!shapes.is_empty(), "Empty guide");
+        FixedGuide { shapes }
+    }
+}
+
+impl Strategy for FixedGuide {
+    fn name(&self) -> String {
+        String::from("guide")
+    }
+
+    fn choose(&mut self, history: &[(Shape, Shape)]) -> Shape {
+        self.shapes[history.len() % self.shapes.len()]
+    }
+}
+
+/// Plays uniformly random shapes, reproducible from a seed.
+pub struct Random {
+    seed: u64,
+    rng: StdRng
+}
+
+impl Random {
+    /// Creates a strategy drawing its shapes from `seed`.
+    pub fn new(seed: u64) -> Random {
+        Random { seed, rng: StdRng::seed_from_u64(seed) }
+    }
+}
+
+impl Strategy for Random {
+    fn name(&self) -> String {
+        format!("random({})", self.seed)
+    }
+
+    fn choose(&mut self, _history: &[(Shape, Shape)]) -> Shape {
+        Shape::from_index(self.rng.gen_range(0..Shape::ALL.len()))
+    }
+}
+
+/// Defeats the shape the opponent played the most so far.
+pub struct FrequencyCounter;
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> String {
+        String::from("frequency")
+    }
+
+    fn choose(&mut self, history: &[(Shape, Shape)]) -> Shape {
+        most_frequent(history.iter().map(|(_, opponent)| opponent))
+            .map(|shape| counter(&shape))
+            .unwrap_or(Shape::Rock)
+    }
+}
+
+/// Predicts the next opponent shape from what followed its last shape so far,
+/// and defeats it.
+pub struct MarkovPredictor;
+
+impl Strategy for MarkovPredictor {
+    fn name(&self) -> String {
+        String::from("markov")
+    }
+
+    fn choose(&mut self, history: &[(Shape, Shape)]) -> Shape {
+        let Some((_, last)) = history.last() else {
+            return Shape::Rock;
+        };
+        let followers = history.windows(2)
+            .filter(|pair| pair[0].1 == *last)
+            .map(|pair| &pair[1].1);
+        most_frequent(followers)
+            .or_else(|| most_frequent(history.iter().map(|(_, opponent)| opponent)))
+            .map(|shape| counter(&shape))
+            .expect("Non empty history")
+    }
+}
+
+/// A round of a match, from the first player point of view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundLog {
+    /// Round number, from 1.
+    pub round: usize,
+    /// Shapes played, as (first, second) player.
+    pub shapes: (Shape, Shape),
+    /// Result of the first player.
+    pub result: Ordering,
+    /// Round scores, as (first, second) player.
+    pub scores: (i32, i32)
+}
+
+/// All rounds played between two players.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchLog {
+    /// Player indexes in the tournament, as (first, second).
+    pub players: (usize, usize),
+    /// Rounds, in order.
+    pub rounds: Vec<RoundLog>
+}
+
+impl MatchLog {
+    /// Returns the total scores, as (first, second) player.
+    pub fn totals(&self) -> (i32, i32) {
+        self.rounds.iter()
+            .fold((0, 0), |(first, second), round| (first + round.scores.0, second + round.scores.1))
+    }
+}
+
+/// Plays `rounds` rounds between `first` and `second`.
+pub fn play_match(first: &mut dyn Strategy, second: &mut dyn Strategy, rounds: usize) -> Vec<RoundLog> {
+    let score_map : HashMap<Shape, i32> = shape_scores();
+    let mut first_history : Vec<(Shape, Shape)> = Vec::with_capacity(rounds);
+    let mut second_history : Vec<(Shape, Shape)> = Vec::with_capacity(rounds);
+    let mut logs = Vec::with_capacity(rounds);
+    for round in 1..=rounds {
+        let first_shape = first.choose(&first_history);
+        let second_shape = second.choose(&second_history);
+        let result = play(&first_shape, &second_shape);
+        logs.push(RoundLog {
+            round,
+            shapes: (first_shape, second_shape),
+            result,
+            scores: (
+                get_score(&score_map, &first_shape, result),
+                get_score(&score_map, &second_shape, result.reverse()))
+        });
+        first_history.push((first_shape, second_shape));
+        second_history.push((second_shape, first_shape));
+    }
+    logs
+}
+
+/// Cumulated results of a player over the tournament.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    /// Strategy name.
+    pub name: String,
+    /// Sum of the round scores.
+    pub score: i32,
+    /// Rounds won.
+    pub wins: usize,
+    /// Rounds ended in a draw.
+    pub draws: usize,
+    /// Rounds lost.
+    pub losses: usize
+}
+
+impl Standing {
+    fn add(&mut self, score: i32, result: Ordering) {
+        self.score += score;
+        match result {
+            Ordering::Greater => self.wins += 1,
+            Ordering::Equal => self.draws += 1,
+            Ordering::Less => self.losses += 1
+        }
+    }
+}
+
+/// Players of a round-robin tournament.
+pub struct Tournament {
+    players: Vec<Box<dyn Strategy>>,
+    rounds: usize
+}
+
+impl Tournament {
+
+    /// Creates a tournament without players, where each match lasts `rounds` rounds.
+    pub fn new(rounds: usize) -> Tournament {
+        Tournament { players: Vec::new(), rounds }
+    }
+
+    /// Adds a player.
+    pub fn add(&mut self, player: Box<dyn Strategy>) -> &mut Tournament {
+        self.players.push(player);
+        self
+    }
+
+    /// Returns the player names, in addition order.
+    pub fn names(&self) -> Vec<String> {
+        self.players.iter().map(|player| player.name()).collect()
+    }
+
+    /// Plays every pair of players once. Returns the match logs, in play
+    /// order, and the leaderboard, best score first.
+    pub fn run(&mut self) -> (Vec<MatchLog>, Vec<Standing>) {
+        let mut standings : Vec<Standing> = self.players.iter()
+            .map(|player| Standing { name: player.name(), score: 0, wins: 0, draws: 0, losses: 0 })
+            .collect();
+        let mut matches = Vec::new();
+        for second in 1..self.players.len() {
+            let (head, tail) = self.players.split_at_mut(second);
+            for (first, first_player) in head.iter_mut().enumerate() {
+                let rounds = play_match(first_player.as_mut(), tail[0].as_mut(), self.rounds);
+                for round in &rounds {
+                    standings[first].add(round.scores.0, round.result);
+                    standings[second].add(round.scores.1, round.result.reverse());
+                }
+                matches.push(MatchLog { players: (first, second), rounds });
+            }
+        }
+        standings.sort_by_key(|standing| -standing.score);
+        (matches, standings)
+    }
+}
+
+#[test]
+fn test_fixed_guide_against_frequency_counter() {
+    let mut guide = FixedGuide::new(vec![Shape::Rock]);
+    let rounds = play_match(&mut guide, &mut FrequencyCounter, 3);
+    // The counter opens with rock, then plays paper against the rocks.
+    assert_eq!(Ordering::Equal, rounds[0].result);
+    assert_eq!((Shape::Rock, Shape::Paper), rounds[1].shapes);
+    assert_eq!((1, 8), rounds[2].scores);
+}
+
+#[test]
+fn test_markov_predictor() {
+    let mut guide = FixedGuide::new(vec![Shape::Rock, Shape::Paper, Shape::Scissor]);
+    let rounds = play_match(&mut guide, &mut MarkovPredictor, 9);
+    // Once the cycle is learnt, every round is won by the predictor.
+    assert!(rounds[4..].iter().all(|round| round.result == Ordering::Less));
+}
+
+#[test]
+fn test_random_is_seeded() {
+    let draw = |seed| {
+        let mut random = Random::new(seed);
+        (0..20).map(|_| random.choose(&[])).collect::<Vec<Shape>>()
+    };
+    assert_eq!(draw(7), draw(7));
+    assert_ne!(draw(7), draw(8));
+}
+
+#[test]
+fn test_tournament() {
+    let mut tournament = Tournament::new(10);
+    tournament
+        .add(Box::new(FixedGuide::new(vec![Shape::Scissor])))
+        .add(Box::new(FrequencyCounter))
+        .add(Box::new(MarkovPredictor));
+    let (matches, standings) = tournament.run();
+    assert_eq!(vec![(0, 1), (0, 2), (1, 2)],
+        matches.iter().map(|log| log.players).collect::<Vec<_>>());
+    assert!(matches.iter().all(|log| log.rounds.len() == 10));
+    assert_eq!("guide", standings[2].name);
+    let rounds : usize = standings.iter().map(|s| s.wins + s.draws + s.losses).sum();
+    assert_eq!(60, rounds);
+}
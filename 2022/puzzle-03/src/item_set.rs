@@ -0,0 +1,154 @@
+//! Sets of item types, stored as a bitmask over the 52 priorities.
+//!
+//! Item types are ASCII letters: the methods taking items panic on any other
+//! byte, like [`get_item_score`].
+
+use std::fmt;
+use std::ops::{BitAnd, BitOr};
+
+use crate::{get_item_score, SCORE_A_LOWERCASE, SCORE_A_UPPERCASE};
+
+/// Returns the item type of a priority, from 1 to 52.
+pub fn get_priority_item(priority: u8) -> u8 {
+    match priority {
+        1..=26 => SCORE_A_LOWERCASE + priority - 1,
+        27..=52 => SCORE_A_UPPERCASE + priority - 27,
+        _ => panic!("Invalid priority {}", priority)
+    }
+}
+
+/// A set of item types. Bit `p - 1` is set when the item of priority `p` is
+/// in the set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    /// The set without any item type.
+    pub const EMPTY : ItemSet = ItemSet(0);
+    /// The set of all 52 item types.
+    pub const ALL : ItemSet = ItemSet((1 << 52) - 1);
+
+    /// Returns the set of the item types of a rucksack part.
+    pub fn from_items(items: &str) -> ItemSet {
+        items.bytes().collect()
+    }
+
+    /// Adds an item type.
+    pub fn insert(&mut self, item: u8) {
+        self.0 |= 1 << (get_item_score(item) - 1);
+    }
+
+    /// Removes an item type.
+    pub fn remove(&mut self, item: u8) {
+        self.0 &= !(1 << (get_item_score(item) - 1));
+    }
+
+    /// Tells whether the set holds an item type.
+    pub fn contains(&self, item: u8) -> bool {
+        self.0 & (1 << (get_item_score(item) - 1)) != 0
+    }
+
+    /// Returns the item types found in both sets.
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    /// Returns the item types found in any set.
+    pub fn union(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    /// Returns the count of item types.
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Tells whether the set has no item type.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the item type of lowest priority.
+    pub fn first(&self) -> Option<u8> {
+        self.iter().next()
+    }
+
+    /// Returns the sum of the item priorities.
+    pub fn priority_total(&self) -> u32 {
+        self.priorities().map(u32::from).sum()
+    }
+
+    /// Returns the item priorities, in increasing order.
+    pub fn priorities(&self) -> impl DoubleEndedIterator<Item = u8> {
+        let bits = self.0;
+        (1..=52).filter(move |priority| bits & (1 << (priority - 1)) != 0)
+    }
+
+    /// Returns the item types, by increasing priority.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = u8> {
+        self.priorities().map(get_priority_item)
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, other: ItemSet) -> ItemSet {
+        self.intersection(other)
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = ItemSet;
+
+    fn bitor(self, other: ItemSet) -> ItemSet {
+        self.union(other)
+    }
+}
+
+impl FromIterator<u8> for ItemSet {
+    fn from_iter<I: IntoIterator<Item = u8>>(items: I) -> ItemSet {
+        let mut set = ItemSet::EMPTY;
+        for item in items {
+            set.insert(item);
+        }
+        set
+    }
+}
+
+impl fmt::Display for ItemSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.iter().try_for_each(|item| write!(f, "{}", item as char))
+    }
+}
+
+#[test]
+fn test_priority_item() {
+    for priority in 1..=52 {
+        assert_eq!(priority, get_item_score(get_priority_item(priority)));
+    }
+}
+
+#[test]
+fn test_set_operations() {
+    let left = ItemSet::from_items("vJrwpWtwJgWr");
+    let right = ItemSet::from_items("hcsFMMfFFhFp");
+    assert_eq!(b"p".to_vec(), (left & right).iter().collect::<Vec<u8>>());
+    assert_eq!(ItemSet::from_items("JWgprtvw"), left);
+    assert_eq!(left.len() + right.len() - 1, (left | right).len());
+    assert_eq!("gprtvwJW", left.to_string());
+    assert_eq!(Some(b'g'), left.first());
+    assert_eq!(7 + 16 + 18 + 20 + 22 + 23 + 36 + 49, left.priority_total());
+    assert_eq!(52, ItemSet::ALL.len());
+    assert!(ItemSet::EMPTY.is_empty());
+}
+
+#[test]
+fn test_insert_remove() {
+    let mut set = ItemSet::EMPTY;
+    set.insert(b'Z');
+    set.insert(b'a');
+    assert!(set.contains(b'Z') && set.contains(b'a') && !set.contains(b'z'));
+    set.remove(b'Z');
+    assert_eq!(vec![1], set.priorities().collect::<Vec<u8>>());
+}
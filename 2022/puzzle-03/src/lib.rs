@@ -0,0 +1,191 @@
+//! Day 3: Rucksack Reorganization
+//!
+//! Finds the items shared between compartments and between elves.
+
+#![warn(missing_docs)]
+
+use aoc_common::{ParseError, Solution};
+
+use rucksack::{BadgeError, Rucksack};
+
+pub mod item_set;
+pub mod planner;
+pub mod rucksack;
+
+/// Returns the item type found in both compartments of a rucksack, the one
+/// of highest priority if several are. Returns `None` when the compartments
+/// have different sizes or share nothing.
+pub fn get_redundant_item(backpack: &str) -> Option<u8> {
+    Rucksack::new(backpack, 2)?.shared_items()
+        .iter()
+        .next_back()
+}
+
+/// Returns the first byte of a string.
+pub fn get_char_byte(s : &str) -> u8 {
+    s.as_bytes()[0]
+}
+
+/// The `a` item type, whose priority is 1.
+pub static SCORE_A_LOWERCASE : u8 = "a".as_bytes()[0];
+/// The `A` item type, whose priority is 27.
+pub static SCORE_A_UPPERCASE : u8 = "A".as_bytes()[0];
+
+/// Returns the priority of an item type. Panics if `c` is not an ASCII letter.
+pub fn get_item_score(c: u8) -> u8 {
+    if c.is_ascii_lowercase() {
+        c - SCORE_A_LOWERCASE + 1
+    } else if c.is_ascii_uppercase() {
+        c - SCORE_A_UPPERCASE + 27
+    } else {
+        panic!("Invalid character {:?}", c);
+    }
+}
+
+/// Checks that a rucksack is made of item types and can be split into two
+/// compartments of the same size.
+pub fn parse_rucksack(line_number: usize, line: &str) -> Result<String, ParseError> {
+    parse_rucksack_with(line_number, line, 2)
+}
+
+/// Checks that a rucksack is made of item types and can be split into
+/// `compartments` compartments of the same size.
+pub fn parse_rucksack_with(line_number: usize, line: &str, compartments: usize) -> Result<String, ParseError> {
+    if let Some((index, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+        return Err(ParseError::new(line_number, index + 1, &c.to_string(), "Invalid item type"));
+    }
+    if Rucksack::new(line, compartments).is_none() {
+        return Err(ParseError::new(line_number, 1, line, "Compartments of different sizes"));
+    }
+    Ok(line.to_string())
+}
+
+/// Count of elves of a group, sharing a badge.
+pub const GROUP_SIZE : usize = 3;
+
+/// Rucksacks contents, one string per elf.
+#[derive(Debug)]
+pub struct Rucksacks {
+    /// Items of each rucksack.
+    pub packs: Vec<String>,
+    /// Count of compartments of each rucksack.
+    pub compartments: usize
+}
+
+impl Rucksacks {
+
+    /// Parses rucksacks split into `compartments` compartments of the same size.
+    pub fn parse_with(input: &str, compartments: usize) -> Result<Rucksacks, ParseError> {
+        let packs = input.lines()
+            .enumerate()
+            .map(|(index, line)| parse_rucksack_with(index + 1, line, compartments))
+            .collect::<Result<Vec<String>, ParseError>>()?;
+        Ok(Rucksacks { packs, compartments })
+    }
+
+    /// Returns the rucksacks split into their compartments.
+    pub fn rucksacks(&self) -> impl Iterator<Item = Rucksack<'_>> {
+        self.packs.iter()
+            .map(|pack| Rucksack::new(pack, self.compartments).expect("Checked at parsing"))
+    }
+
+    /// Returns the total score of redundant items, the items found in every
+    /// compartment of a rucksack.
+    pub fn redundancy_total(&self) -> i32 {
+        self.rucksacks()
+            .map(|rucksack| rucksack.shared_items().priority_total() as i32)
+            .sum()
+    }
+
+    /// Returns the badge of each group of `group_size` elves.
+    pub fn badges(&self, group_size: usize) -> Vec<Result<u8, BadgeError>> {
+        rucksack::badges(&self.packs, group_size)
+    }
+
+    /// Returns the total score of badges, by groups of 3 elves. Groups
+    /// without a single badge count for nothing.
+    pub fn badges_total(&self) -> i32 {
+        self.badges(GROUP_SIZE).into_iter()
+            .filter_map(Result::ok)
+            .map(|badge| get_item_score(badge) as i32)
+            .sum()
+    }
+}
+
+impl Solution for Rucksacks {
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    /// Parses rucksacks of 2 compartments, every group of 3 elves sharing
+    /// a single badge.
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let rucksacks = Rucksacks::parse_with(input, 2)?;
+        if let Some(error) = rucksacks.badges(GROUP_SIZE).into_iter().find_map(Result::err) {
+            let line = error.group() * GROUP_SIZE;
+            return Err(ParseError::new(line + 1, 1, &rucksacks.packs[line], &error.to_string()));
+        }
+        Ok(rucksacks)
+    }
+
+    fn part1(&self) -> i32 {
+        self.redundancy_total()
+    }
+
+    fn part2(&self) -> i32 {
+        self.badges_total()
+    }
+}
+
+#[test]
+fn test_example() {
+    let rucksacks = Rucksacks::parse("vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+").expect("Valid input");
+    assert_eq!(157, rucksacks.part1());
+    assert_eq!(70, rucksacks.part2());
+}
+
+#[test]
+fn test_item_score() {
+    assert_eq!(1, get_item_score(SCORE_A_LOWERCASE));
+    assert_eq!(26, get_item_score(get_char_byte("z")));
+    assert_eq!(27, get_item_score(SCORE_A_UPPERCASE));
+    assert_eq!(52, get_item_score(get_char_byte("Z")));
+}
+
+#[test]
+fn test_redundant_item() {
+    assert_eq!(Some(b'p'), get_redundant_item("vJrwpWtwJgWrhcsFMMfFFhFp"));
+    assert_eq!(None, get_redundant_item("abcd"));
+    assert_eq!(None, get_redundant_item("aba"));
+}
+
+#[test]
+fn test_invalid_rucksack() {
+    let error = parse_rucksack(4, "abc").expect_err("Odd length");
+    assert_eq!(4, error.line);
+    let error = parse_rucksack(5, "ab1d").expect_err("Invalid item");
+    assert_eq!((5, 3), (error.line, error.column));
+}
+
+#[test]
+fn test_three_compartments() {
+    let rucksacks = Rucksacks::parse_with("abcAbcaBc\nxyzxYx\n", 3).expect("Valid input");
+    assert_eq!(3 + 24, rucksacks.redundancy_total());
+    let error = Rucksacks::parse_with("abcd\n", 3).expect_err("Invalid size");
+    assert_eq!("Compartments of different sizes", error.message);
+}
+
+#[test]
+fn test_invalid_groups() {
+    let error = Rucksacks::parse("abab\ncdcd\nefef\n").expect_err("No badge");
+    assert_eq!((1, "group 1: no badge"), (error.line, error.message.as_str()));
+    let error = Rucksacks::parse("abab\nacac\nadad\nxyxy\n").expect_err("Incomplete group");
+    assert_eq!((4, "xyxy", "group 2: only 1 rucksacks"), (error.line, error.text.as_str(), error.message.as_str()));
+    let rucksacks = Rucksacks::parse_with("abab\ncdcd\nefef\n", 2).expect("Valid rucksacks");
+    assert_eq!(0, rucksacks.badges_total());
+}
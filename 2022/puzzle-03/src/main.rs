@@ -1,91 +1,105 @@
-use std::{io::{self, BufRead}, collections::HashSet};
+use std::env;
+use std::io::{self, Read};
+use std::process::ExitCode;
 
-use intersection;
-//mod intersection;
+use puzzle_03::planner::plan;
+use puzzle_03::{get_item_score, Rucksacks};
 
-fn to_set(s : &str) -> HashSet<u8> {
-    s.as_bytes().iter().map(|c| *c).collect::<HashSet<u8>>()
-}
+static DEFAULT_COMPARTMENTS : usize = 2;
+static DEFAULT_GROUP_SIZE : usize = puzzle_03::GROUP_SIZE;
 
-fn get_redundant_item(backpack: &String) -> u8 {
-    let separator = backpack.len() / 2;
-    let left_pack = &backpack[..separator];
-    assert_eq!(left_pack.len(), separator);
-    let right_pack = &backpack[separator..];
-    assert_eq!(right_pack.len(), separator);
-    let left_set = to_set(left_pack);
-    let right_set = to_set(right_pack);
-    let duplicate = left_set.intersection(&right_set)
-        .last()
-        .expect("No redundant item type in backpack");
-    *duplicate
+/// Options of the program.
+struct Args {
+    compartments : usize,
+    group_size : usize,
+    badges : bool
 }
 
-fn get_char_byte(s : &str) -> u8 {
-    s.as_bytes()[0]
+fn parse_args(args : &[String]) -> Result<Args, String> {
+    let mut parsed = Args { compartments: DEFAULT_COMPARTMENTS, group_size: DEFAULT_GROUP_SIZE, badges: false };
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--badges" {
+            parsed.badges = true;
+            continue;
+        }
+        let value = iter.next().ok_or(format!("Missing value for {}", arg))?;
+        let count = value.parse::<usize>().ok().filter(|count| *count > 0)
+            .ok_or(format!("Invalid count {}", value))?;
+        match arg.as_str() {
+            "--compartments" => parsed.compartments = count,
+            "--group-size" => parsed.group_size = count,
+            _ => return Err(format!("Unknown option {}", arg))
+        }
+    }
+    Ok(parsed)
 }
 
-static SCORE_A_LOWERCASE : u8 = "a".as_bytes()[0];
-static SCORE_A_UPPERCASE : u8 = "A".as_bytes()[0];
-
-fn get_item_score(c: u8) -> u8 {
-    if c.is_ascii_lowercase() {
-        c - SCORE_A_LOWERCASE + 1
-    } else if c.is_ascii_uppercase() {
-        c - SCORE_A_UPPERCASE + 27
-    } else {
-        panic!("Invalid character {:?}", c);
+fn print_totals(rucksacks : &Rucksacks, args : &Args) -> ExitCode {
+    println!("Redondant items total is {}", rucksacks.redundancy_total());
+    let mut badges_total = 0;
+    for badge in rucksacks.badges(args.group_size) {
+        match badge {
+            Ok(badge) => badges_total += get_item_score(badge) as i32,
+            Err(error) => eprintln!("Invalid {}", error)
+        }
     }
+    println!("Badges items total is {}", badges_total);
+    ExitCode::SUCCESS
 }
 
-fn get_badge_score(packs : &Vec<String>) -> i32 {
-    let sets : Vec<HashSet<u8>> = packs.iter()
-        .map(|s| to_set((*s).as_str()))
-        .collect();
-    let badge_set = intersection::hash_set::intersection(sets);
-    assert_eq!(1, badge_set.len());
-    let badge_char = *(badge_set.iter().last().expect("No badge item"));
-    get_item_score(badge_char) as i32
+/// Prints the rearranged rucksacks, and the swaps on the error output.
+fn print_plan(rucksacks : &Rucksacks, args : &Args) -> ExitCode {
+    let plan = plan(&rucksacks.packs, args.badges.then_some(args.group_size));
+    for swap in &plan.swaps {
+        eprintln!("{}", swap);
+    }
+    eprintln!("{} swaps", plan.swaps.len());
+    for unsolved in &plan.unsolved {
+        eprintln!("Unsolved {}", unsolved);
+    }
+    for pack in &plan.packs {
+        println!("{}", pack);
+    }
+    if plan.unsolved.is_empty() { ExitCode::SUCCESS } else { ExitCode::FAILURE }
 }
 
-fn main() {
-
-    let mut redundancy_total : i32 = 0;
-    let mut badges_total : i32 = 0;
-
-    // Checking
-    assert_eq!(1, get_item_score(SCORE_A_LOWERCASE));
-    assert_eq!(26, get_item_score(get_char_byte("z")));
-    assert_eq!(27, get_item_score(SCORE_A_UPPERCASE));
-    assert_eq!(52, get_item_score(get_char_byte("Z")));
+fn main() -> ExitCode {
+
+    // Arguments
+    let args : Vec<String> = env::args().skip(1).collect();
+    let planning = args.first().is_some_and(|arg| arg == "plan");
+    let options = if planning { &args[1..] } else { &args[..] };
+    let args = match parse_args(options) {
+        Ok(args) if planning && args.compartments != 2 => {
+            eprintln!("Only rucksacks of 2 compartments can be planned");
+            return ExitCode::FAILURE;
+        },
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!("Usage: puzzle-03 [plan [--badges]] [--compartments 2] [--group-size 3]");
+            return ExitCode::FAILURE;
+        }
+    };
 
     // Reading
-    let stdin : io::Stdin = io::stdin();
-    let mut lines = stdin.lock().lines();
-    let mut group_counter = 0;
-    let mut group_packs : Vec<String> = Vec::with_capacity(3);
-    loop {
-        if let Some(line) = lines.next() {
-            let line_str = line.expect("Unable to read line");
-
-            let item_type = get_redundant_item(&line_str);
-            let score = get_item_score(item_type);
-            redundancy_total += score as i32;
-
-            group_packs.push(String::from(line_str.as_str()));
-            group_counter += 1;
-            if group_counter == 3 {
-                badges_total += get_badge_score(&group_packs);
-                group_packs.clear();
-                group_counter = 0;
-            }
-
-        } else {
-            break;
-        }
+    let mut input = String::new();
+    if let Err(error) = io::stdin().read_to_string(&mut input) {
+        eprintln!("Unable to read input: {}", error);
+        return ExitCode::FAILURE;
     }
+    let rucksacks = match Rucksacks::parse_with(&input, args.compartments) {
+        Ok(rucksacks) => rucksacks,
+        Err(error) => {
+            eprintln!("Invalid input at {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
 
-    println!("Redondant items total is {}", redundancy_total);
-    println!("Badges items total is {}", badges_total);
-
+    if planning {
+        print_plan(&rucksacks, &args)
+    } else {
+        print_totals(&rucksacks, &args)
+    }
 }
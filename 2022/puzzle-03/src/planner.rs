@@ -0,0 +1,345 @@
+//! Reorganisation of the rucksacks, by swapping items.
+//!
+//! Inside a rucksack, the fewest swaps making both compartments disjoint are
+//! found by choosing the item types kept in the left compartment: a subset sum
+//! over the type counts, where each right item of a type kept on the left costs
+//! one swap. Badges are then fixed group by group, by greedily swapping items
+//! between rucksacks of the group while keeping the compartments disjoint.
+
+use std::fmt;
+
+use crate::get_item_score;
+use crate::item_set::ItemSet;
+
+/// Returns the position swaps, as (left, right), making both compartments of
+/// `pack` disjoint with the fewest swaps. Returns `None` when the item types
+/// cannot be split into two halves, or when `pack` has an odd length or holds
+/// something else than item types.
+pub fn plan_compartments(pack: &[u8]) -> Option<Vec<(usize, usize)>> {
+    if !pack.len().is_multiple_of(2) || !pack.iter().all(u8::is_ascii_alphabetic) {
+        return None;
+    }
+    let half = pack.len() / 2;
+    if half == 0 {
+        return Some(Vec::new());
+    }
+    let mut counts = [[0usize; 2]; 52];
+    for (position, item) in pack.iter().enumerate() {
+        counts[get_item_score(*item) as usize - 1][position / half] += 1;
+    }
+    let types : Vec<usize> = (0..52).filter(|t| counts[*t] != [0, 0]).collect();
+
+    // costs[i][s]: fewest swaps when the types[..i] kept on the left hold s items.
+    let mut costs = vec![vec![usize::MAX; half + 1]; types.len() + 1];
+    costs[0][0] = 0;
+    for (i, t) in types.iter().enumerate() {
+        let [left, right] = counts[*t];
+        for kept in 0..=half {
+            let cost = costs[i][kept];
+            if cost == usize::MAX {
+                continue;
+            }
+            costs[i + 1][kept] = costs[i + 1][kept].min(cost);
+            if kept + left + right <= half {
+                let with_type = &mut costs[i + 1][kept + left + right];
+                *with_type = (*with_type).min(cost + right);
+            }
+        }
+    }
+    if costs[types.len()][half] == usize::MAX {
+        return None;
+    }
+
+    let mut on_left = [false; 52];
+    let mut kept = half;
+    for i in (0..types.len()).rev() {
+        if costs[i][kept] != costs[i + 1][kept] {
+            let t = types[i];
+            on_left[t] = true;
+            kept -= counts[t][0] + counts[t][1];
+        }
+    }
+    let is_left = |item: &u8| on_left[get_item_score(*item) as usize - 1];
+    let to_right = (0..half).filter(|p| !is_left(&pack[*p]));
+    let to_left = (half..pack.len()).filter(|p| is_left(&pack[*p]));
+    Some(to_right.zip(to_left).collect())
+}
+
+/// Exchange of two items, each identified by (rucksack, position).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Swap {
+    /// Place of the first item, before the swap.
+    pub first: (usize, usize),
+    /// Place of the second item, before the swap.
+    pub second: (usize, usize),
+    /// Items swapped, as (first, second).
+    pub items: (u8, u8)
+}
+
+impl fmt::Display for Swap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "swap {} (rucksack {}, position {}) with {} (rucksack {}, position {})",
+            self.items.0 as char, self.first.0 + 1, self.first.1 + 1,
+            self.items.1 as char, self.second.0 + 1, self.second.1 + 1)
+    }
+}
+
+/// Part of the reorganisation that could not be done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unsolved {
+    /// The compartments of a rucksack cannot be made disjoint.
+    Compartments {
+        /// Rucksack index, from 0.
+        rucksack: usize
+    },
+    /// No swap found to leave a single badge to a group.
+    Badge {
+        /// Group index, from 0.
+        group: usize
+    },
+    /// Badges were asked for groups of 0 elves.
+    EmptyGroups
+}
+
+impl fmt::Display for Unsolved {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Unsolved::Compartments { rucksack } =>
+                write!(f, "rucksack {}: compartments cannot be made disjoint", rucksack + 1),
+            Unsolved::Badge { group } =>
+                write!(f, "group {}: no single badge found", group + 1),
+            Unsolved::EmptyGroups =>
+                write!(f, "groups of 0 elves cannot share a badge")
+        }
+    }
+}
+
+/// Result of the reorganisation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    /// Rearranged rucksacks.
+    pub packs: Vec<String>,
+    /// Swaps, in order.
+    pub swaps: Vec<Swap>,
+    /// What could not be done.
+    pub unsolved: Vec<Unsolved>
+}
+
+/// Rucksack contents during the planning.
+struct Planner {
+    packs: Vec<Vec<u8>>,
+    swaps: Vec<Swap>
+}
+
+impl Planner {
+
+    fn swap(&mut self, first: (usize, usize), second: (usize, usize)) {
+        let items = (self.packs[first.0][first.1], self.packs[second.0][second.1]);
+        self.packs[first.0][first.1] = items.1;
+        self.packs[second.0][second.1] = items.0;
+        self.swaps.push(Swap { first, second, items });
+    }
+
+    fn set(&self, rucksack: usize) -> ItemSet {
+        self.packs[rucksack].iter().copied().collect()
+    }
+
+    fn count(&self, rucksack: usize, item: u8) -> usize {
+        self.packs[rucksack].iter().filter(|i| **i == item).count()
+    }
+
+    /// Tells whether `item` can be put at `position` without being found in
+    /// the other compartment.
+    fn fits(&self, (rucksack, position): (usize, usize), item: u8) -> bool {
+        let pack = &self.packs[rucksack];
+        let half = pack.len() / 2;
+        let other = if position < half { &pack[half..] } else { &pack[..half] };
+        !other.contains(&item)
+    }
+
+    /// Tells whether `item`, given by `giver` to `receiver`, would then be
+    /// shared by the whole group.
+    fn shared_after(&self, members: &[usize], receiver: usize, giver: usize, item: u8) -> bool {
+        members.iter().all(|member| {
+            if *member == receiver {
+                true
+            } else if *member == giver {
+                self.count(giver, item) > 1
+            } else {
+                self.set(*member).contains(item)
+            }
+        })
+    }
+
+    fn positions(&self, rucksack: usize, item: u8) -> Vec<usize> {
+        (0..self.packs[rucksack].len()).filter(|p| self.packs[rucksack][*p] == item).collect()
+    }
+
+    /// Finds an item of another member, not in `forbidden`, that can be
+    /// swapped with the item at `place`.
+    fn partner(&self, members: &[usize], place: (usize, usize), forbidden: ItemSet) -> Option<(usize, usize)> {
+        let item = self.packs[place.0][place.1];
+        members.iter()
+            .filter(|member| **member != place.0)
+            .flat_map(|member| (0..self.packs[*member].len()).map(move |position| (*member, position)))
+            .find(|other| {
+                let other_item = self.packs[other.0][other.1];
+                !forbidden.contains(other_item)
+                    && self.fits(place, other_item)
+                    && self.fits(*other, item)
+                    && !self.shared_after(members, place.0, other.0, other_item)
+            })
+    }
+
+    /// Brings an item type held by all members but one to the last member.
+    fn add_badge(&mut self, members: &[usize]) -> bool {
+        for badge in ItemSet::ALL.iter() {
+            let missing : Vec<usize> = members.iter().copied()
+                .filter(|member| !self.set(*member).contains(badge))
+                .collect();
+            let [target] = missing[..] else {
+                continue;
+            };
+            for source in members.iter().copied().filter(|member| self.count(*member, badge) > 1) {
+                for position in self.positions(source, badge) {
+                    let found = (0..self.packs[target].len()).map(|q| (target, q)).find(|other| {
+                        let other_item = self.packs[other.0][other.1];
+                        self.fits((source, position), other_item)
+                            && self.fits(*other, badge)
+                            && !self.shared_after(members, source, target, other_item)
+                    });
+                    if let Some(other) = found {
+                        self.swap((source, position), other);
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// Removes all shared item types but one, from the member holding them the least.
+    fn remove_badges(&mut self, members: &[usize], shared: ItemSet) -> bool {
+        let removal_cost = |badge: u8| members.iter().map(|m| self.count(*m, badge)).min().unwrap_or(0);
+        let keep = shared.iter()
+            .rev()
+            .max_by_key(|badge| removal_cost(*badge))
+            .expect("Shared items");
+        for badge in shared.iter().filter(|badge| *badge != keep) {
+            let holder = *members.iter()
+                .min_by_key(|member| self.count(**member, badge))
+                .expect("Group members");
+            for position in self.positions(holder, badge) {
+                match self.partner(members, (holder, position), shared) {
+                    Some(other) => self.swap((holder, position), other),
+                    None => return false
+                }
+            }
+        }
+        true
+    }
+
+    fn shared(&self, members: &[usize]) -> ItemSet {
+        members.iter().fold(ItemSet::ALL, |shared, member| shared & self.set(*member))
+    }
+
+    /// Leaves a single item type shared by all `members`.
+    fn fix_badge(&mut self, members: &[usize]) -> bool {
+        let shared = self.shared(members);
+        match shared.len() {
+            0 => self.add_badge(members),
+            1 => true,
+            _ => self.remove_badges(members, shared)
+        };
+        self.shared(members).len() == 1
+    }
+}
+
+/// Makes both compartments of every rucksack disjoint with the fewest swaps.
+/// When `group_size` is given, then gives a single badge to every group of
+/// `group_size` elves, swapping items between the rucksacks of the group.
+///
+/// Rucksacks holding something else than item types are left unchanged, as
+/// well as their group.
+pub fn plan<S: AsRef<str>>(packs: &[S], group_size: Option<usize>) -> Plan {
+    let mut planner = Planner {
+        packs: packs.iter().map(|pack| pack.as_ref().as_bytes().to_vec()).collect(),
+        swaps: Vec::new()
+    };
+    let mut unsolved = Vec::new();
+    for rucksack in 0..planner.packs.len() {
+        match plan_compartments(&planner.packs[rucksack]) {
+            Some(positions) => positions.into_iter()
+                .for_each(|(left, right)| planner.swap((rucksack, left), (rucksack, right))),
+            None => unsolved.push(Unsolved::Compartments { rucksack })
+        }
+    }
+    match group_size {
+        Some(0) => unsolved.push(Unsolved::EmptyGroups),
+        Some(size) => for (group, start) in (0..planner.packs.len()).step_by(size).enumerate() {
+            let members : Vec<usize> = (start..planner.packs.len().min(start + size)).collect();
+            let items_only = members.iter().all(|member| planner.packs[*member].iter().all(u8::is_ascii_alphabetic));
+            if members.len() < size || !items_only || !planner.fix_badge(&members) {
+                unsolved.push(Unsolved::Badge { group });
+            }
+        },
+        None => ()
+    }
+    Plan {
+        packs: planner.packs.into_iter()
+            .map(|pack| String::from_utf8(pack).expect("Item types"))
+            .collect(),
+        swaps: planner.swaps,
+        unsolved
+    }
+}
+
+#[cfg(test)]
+fn disjoint(pack: &str) -> bool {
+    let (left, right) = pack.split_at(pack.len() / 2);
+    (ItemSet::from_items(left) & ItemSet::from_items(right)).is_empty()
+}
+
+#[test]
+fn test_plan_compartments() {
+    assert_eq!(Some(vec![]), plan_compartments(b"abcd"));
+    // Keeping `a` on the left moves a single `a` from the right.
+    assert_eq!(Some(vec![(2, 5)]), plan_compartments(b"aabbba"));
+    // Three `a` cannot fit in a compartment of 2.
+    assert_eq!(None, plan_compartments(b"aaab"));
+    assert_eq!(None, plan_compartments(b"abc"));
+    assert_eq!(None, plan_compartments(b"a1"));
+}
+
+#[test]
+fn test_plan_example() {
+    let packs = ["vJrwpWtwJgWrhcsFMMfFFhFp", "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL", "PmmdzqPrVvPwwTWBwg"];
+    let plan = plan(&packs, None);
+    assert!(plan.unsolved.is_empty());
+    assert!(plan.packs.iter().all(|pack| disjoint(pack)));
+    // One swap per redundant item type, except `L` found twice on the right.
+    assert_eq!(4, plan.swaps.len());
+    for (pack, planned) in packs.iter().zip(&plan.packs) {
+        assert_eq!(ItemSet::from_items(pack), ItemSet::from_items(planned));
+    }
+}
+
+#[test]
+fn test_plan_badges() {
+    // Both `x` and `y` are shared by the first group, nothing by the second.
+    let packs = ["xayb", "xcyd", "xeyf", "aacd", "aefg", "hijk"];
+    let plan = plan(&packs, Some(3));
+    assert!(plan.unsolved.is_empty(), "{:?}", plan.unsolved);
+    assert!(plan.packs.iter().all(|pack| disjoint(pack)));
+    for group in plan.packs.chunks(3) {
+        assert_eq!(1, crate::rucksack::shared_items(group.iter().map(String::as_str)).len());
+    }
+}
+
+#[test]
+fn test_plan_invalid() {
+    let planned = plan(&["ab", "a-c", "ab", "bc"], Some(2));
+    assert_eq!(vec![Unsolved::Compartments { rucksack: 1 }, Unsolved::Badge { group: 0 }], planned.unsolved);
+    assert_eq!("a-c", planned.packs[1]);
+    assert_eq!(vec![Unsolved::EmptyGroups], plan(&["ab"], Some(0)).unsolved);
+}
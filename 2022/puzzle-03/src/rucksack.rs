@@ -0,0 +1,151 @@
+//! Rucksacks with any number of compartments, grouped by any number of elves.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::item_set::ItemSet;
+
+/// Returns the item types found in every one of `parts`, which must hold
+/// item types only.
+pub fn shared_items<'a>(parts: impl IntoIterator<Item = &'a str>) -> ItemSet {
+    let mut parts = parts.into_iter();
+    match parts.next() {
+        Some(first) => parts.fold(ItemSet::from_items(first), |shared, part| shared & ItemSet::from_items(part)),
+        None => ItemSet::EMPTY
+    }
+}
+
+/// Items of an elf, split into compartments of the same size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rucksack<'a> {
+    items: &'a str,
+    compartments: usize
+}
+
+impl<'a> Rucksack<'a> {
+
+    /// Splits `items` into `compartments` parts. Returns `None` when there is
+    /// no compartment, when they would not have the same size or when an item
+    /// is not an item type.
+    pub fn new(items: &'a str, compartments: usize) -> Option<Rucksack<'a>> {
+        if compartments == 0 || items.is_empty() || !items.len().is_multiple_of(compartments)
+            || !items.bytes().all(|item| item.is_ascii_alphabetic()) {
+            None
+        } else {
+            Some(Rucksack { items, compartments })
+        }
+    }
+
+    /// Returns all items.
+    pub fn items(&self) -> &'a str {
+        self.items
+    }
+
+    /// Returns the items of each compartment.
+    pub fn compartments(&self) -> impl Iterator<Item = &'a str> {
+        let size = self.items.len() / self.compartments;
+        let items = self.items;
+        (0..self.compartments).map(move |index| &items[index * size..(index + 1) * size])
+    }
+
+    /// Returns the item types found in every compartment.
+    pub fn shared_items(&self) -> ItemSet {
+        shared_items(self.compartments())
+    }
+}
+
+/// Reason why a group of elves has no single badge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BadgeError {
+    /// The last group has fewer rucksacks than the group size.
+    IncompleteGroup {
+        /// Group index, from 0.
+        group: usize,
+        /// Count of rucksacks in the group.
+        size: usize
+    },
+    /// No item type is shared by the whole group.
+    NoBadge {
+        /// Group index, from 0.
+        group: usize
+    },
+    /// Several item types are shared by the whole group.
+    MultipleBadges {
+        /// Group index, from 0.
+        group: usize,
+        /// Shared item types.
+        items: ItemSet
+    }
+}
+
+impl BadgeError {
+    /// Returns the index of the group, from 0.
+    pub fn group(&self) -> usize {
+        match self {
+            BadgeError::IncompleteGroup { group, .. }
+                | BadgeError::NoBadge { group }
+                | BadgeError::MultipleBadges { group, .. } => *group
+        }
+    }
+}
+
+impl fmt::Display for BadgeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BadgeError::IncompleteGroup { group, size } =>
+                write!(f, "group {}: only {} rucksacks", group + 1, size),
+            BadgeError::NoBadge { group } =>
+                write!(f, "group {}: no badge", group + 1),
+            BadgeError::MultipleBadges { group, items } =>
+                write!(f, "group {}: several badges {}", group + 1, items)
+        }
+    }
+}
+
+impl Error for BadgeError {}
+
+/// Returns the badge of each group of `group_size` consecutive rucksacks.
+/// `group_size` must not be 0, and `packs` must hold item types only.
+pub fn badges<S: AsRef<str>>(packs: &[S], group_size: usize) -> Vec<Result<u8, BadgeError>> {
+    packs.chunks(group_size)
+        .enumerate()
+        .map(|(group, packs)| {
+            if packs.len() < group_size {
+                return Err(BadgeError::IncompleteGroup { group, size: packs.len() });
+            }
+            let items = shared_items(packs.iter().map(AsRef::as_ref));
+            match items.len() {
+                0 => Err(BadgeError::NoBadge { group }),
+                1 => Ok(items.first().expect("One badge")),
+                _ => Err(BadgeError::MultipleBadges { group, items })
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_compartments() {
+    let rucksack = Rucksack::new("abcAbcaBc", 3).expect("Same size");
+    assert_eq!(vec!["abc", "Abc", "aBc"], rucksack.compartments().collect::<Vec<_>>());
+    assert_eq!(ItemSet::from_items("c"), rucksack.shared_items());
+    assert_eq!(None, Rucksack::new("abcd", 3));
+    assert_eq!(None, Rucksack::new("", 2));
+    assert_eq!(None, Rucksack::new("ab1d", 2));
+}
+
+#[test]
+fn test_shared_items() {
+    assert_eq!(ItemSet::from_items("aB"), shared_items(["BxaC", "aBy"]));
+    assert!(shared_items(["ab", "cd"]).is_empty());
+}
+
+#[test]
+fn test_badges() {
+    let packs = ["ab", "bc", "xy", "xy", "ab", "cd", "ab"];
+    assert_eq!(vec![
+        Ok(b'b'),
+        Err(BadgeError::MultipleBadges { group: 1, items: ItemSet::from_items("xy") }),
+        Err(BadgeError::NoBadge { group: 2 }),
+        Err(BadgeError::IncompleteGroup { group: 3, size: 1 })],
+        badges(&packs, 2));
+}
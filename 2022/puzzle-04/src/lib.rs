@@ -0,0 +1,129 @@
+//! Day 4: Camp Cleanup
+//!
+//! Finds the overlapping section assignments of pairs of elves.
+
+#![warn(missing_docs)]
+
+use aoc_common::{ParseError, Solution};
+
+pub mod overlaps;
+pub mod range;
+pub mod range_set;
+use overlaps::OverlapPair;
+use range::Range;
+use range_set::Coverage;
+
+/// Parses a line made of two comma-separated ranges.
+pub fn parse_line(line_number : usize, line : &str) -> Result<(Range<u32>, Range<u32>), ParseError> {
+    let (left, right) = line.split_once(',')
+        .ok_or_else(|| ParseError::new(line_number, 1, line, "No comma"))?;
+    let parse_range = |token : &str| token.parse::<Range<u32>>()
+        .map_err(|error| ParseError::in_line(line_number, line, token, &error.to_string()));
+    Ok((parse_range(left)?, parse_range(right)?))
+}
+
+/// Pairs of section assignments, one pair per line.
+#[derive(Debug)]
+pub struct Assignments {
+    /// Ranges of each pair of elves.
+    pub pairs: Vec<(Range<u32>, Range<u32>)>
+}
+
+impl Assignments {
+
+    /// Parses every valid line, and returns the errors of the invalid ones.
+    pub fn parse_lenient(input: &str) -> (Assignments, Vec<ParseError>) {
+        let mut pairs = Vec::new();
+        let mut errors = Vec::new();
+        for (index, line) in input.lines().enumerate() {
+            match parse_line(index + 1, line) {
+                Ok(pair) => pairs.push(pair),
+                Err(error) => errors.push(error)
+            }
+        }
+        (Assignments { pairs }, errors)
+    }
+
+    /// Returns the count of pairs where one range fully contains the other.
+    pub fn full_overlap_count(&self) -> usize {
+        self.pairs.iter()
+            .filter(|(l, r)| range::fully_overlap(l, r))
+            .count()
+    }
+
+    /// Returns the count of pairs where ranges overlap.
+    pub fn overlap_count(&self) -> usize {
+        self.pairs.iter()
+            .filter(|(l, r)| range::overlap(l, r))
+            .count()
+    }
+
+    /// Returns the ranges of every elf, in input order.
+    pub fn ranges(&self) -> Vec<Range<u32>> {
+        self.pairs.iter()
+            .flat_map(|(l, r)| [*l, *r])
+            .collect()
+    }
+
+    /// Returns every pair of elves whose ranges overlap, including the pairs
+    /// of a same line. Elves are indexed as in [`Assignments::ranges`].
+    pub fn overlapping_pairs(&self) -> Vec<OverlapPair> {
+        overlaps::overlapping_pairs(&self.ranges())
+    }
+
+    /// Returns the coverage of the sections by all elves, or `None` when
+    /// there is no assignment.
+    pub fn coverage(&self) -> Option<Coverage<u32>> {
+        range_set::coverage(&self.ranges())
+    }
+}
+
+impl Solution for Assignments {
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let pairs = input.lines()
+            .enumerate()
+            .map(|(index, line)| parse_line(index + 1, line))
+            .collect::<Result<Vec<(Range<u32>, Range<u32>)>, ParseError>>()?;
+        Ok(Assignments { pairs })
+    }
+
+    fn part1(&self) -> usize {
+        self.full_overlap_count()
+    }
+
+    fn part2(&self) -> usize {
+        self.overlap_count()
+    }
+}
+
+#[test]
+fn test_example() {
+    let assignments = Assignments::parse("2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n").expect("Valid input");
+    assert_eq!(2, assignments.part1());
+    assert_eq!(4, assignments.part2());
+    let coverage = assignments.coverage().expect("Assignments");
+    assert_eq!("2-9", coverage.covered.to_string());
+    assert_eq!((8, "6-6".to_string()), (coverage.max_depth, coverage.deepest.to_string()));
+}
+
+#[test]
+fn test_invalid_line() {
+    let error = parse_line(7, "2-4;6-8").expect_err("No comma");
+    assert_eq!((7, 1), (error.line, error.column));
+    let error = parse_line(8, "2-4,6_8").expect_err("No dash");
+    assert_eq!((8, 5), (error.line, error.column));
+    assert_eq!("6_8", error.text);
+    let error = parse_line(9, "2-4,8-6").expect_err("Decreasing");
+    assert_eq!((9, 5, "Lower bound above upper bound"), (error.line, error.column, error.message.as_str()));
+}
+
+#[test]
+fn test_parse_lenient() {
+    let (assignments, errors) = Assignments::parse_lenient("2-4,6-8\n2-4\n300-400,350-360\n5-3,1-2\n");
+    assert_eq!(2, assignments.pairs.len());
+    assert_eq!(1, assignments.full_overlap_count());
+    assert_eq!(vec![2, 4], errors.iter().map(|error| error.line).collect::<Vec<_>>());
+}
@@ -1,38 +1,67 @@
-use std::io::{self, BufRead};
+use std::env;
+use std::io::{self, Read};
+use std::process::ExitCode;
 
-mod range;
-use range::Range;
+use puzzle_04::Assignments;
 
-fn parse_line(line : &String) -> (Range, Range) {
-    let (left, right) = line.split_once(",").expect("No comma?");
-    (
-        range::from_str(left),
-        range::from_str(right)
-    )
+fn print_coverage(assignments : &Assignments) -> ExitCode {
+    let Some(coverage) = assignments.coverage() else {
+        eprintln!("No assignment");
+        return ExitCode::FAILURE;
+    };
+    let uncovered : Vec<String> = coverage.uncovered.iter().map(|range| range.to_string()).collect();
+    println!("Covered sections {} ({} sections)", coverage.covered, coverage.covered.len());
+    println!("Uncovered sections {}", if uncovered.is_empty() { String::from("none") } else { uncovered.join(", ") });
+    println!("Sections of several elves {} ({} sections)", coverage.multiple, coverage.multiple.len());
+    println!("Maximum overlap depth {} at {}", coverage.max_depth, coverage.deepest);
+    ExitCode::SUCCESS
 }
 
-fn main() {
+/// Prints the overlapping pairs of elves, each elf being named by its line
+/// and its side in the line.
+fn print_pairs(assignments : &Assignments) -> ExitCode {
+    let ranges = assignments.ranges();
+    let name = |index : usize| format!("{} (line {} {})", ranges[index], index / 2 + 1,
+        if index.is_multiple_of(2) { "left" } else { "right" });
+    let mut pairs = assignments.overlapping_pairs();
+    pairs.sort_by_key(|pair| (pair.first.min(pair.second), pair.first.max(pair.second)));
+    for pair in &pairs {
+        let (first, second) = (pair.first.min(pair.second), pair.first.max(pair.second));
+        let relation = if pair.contained { "contains or is contained by" } else { "overlaps" };
+        println!("{} {} {}", name(first), relation, name(second));
+    }
+    println!("{} overlapping pairs ({} with containment)", pairs.len(), pairs.iter().filter(|pair| pair.contained).count());
+    ExitCode::SUCCESS
+}
+
+fn main() -> ExitCode {
+
+    // Argument
+    let args : Vec<String> = env::args().skip(1).collect();
+    let mode = args.first().map(String::as_str);
+    if !matches!(mode, None | Some("coverage") | Some("pairs")) {
+        eprintln!("Usage: puzzle-04 [coverage|pairs]");
+        return ExitCode::FAILURE;
+    }
 
     // Reading
-    let mut full_overlap_count: i32 = 0;
-    let mut overlap_count : i32 = 0;
-    let stdin : io::Stdin = io::stdin();
-    let mut lines = stdin.lock().lines();
-    loop {
-        if let Some(line) = lines.next() {
-            let line_str = line.expect("Unable to read line");
-            let (l, r) = parse_line(&line_str);
-            if range::fully_overlap(&l, &r) {
-                full_overlap_count += 1;
-            }
-            if range::overlap(&l, &r) {
-                overlap_count += 1;
-            }
-        }
-        else {
-            break;
-        }
+    let mut input = String::new();
+    if let Err(error) = io::stdin().read_to_string(&mut input) {
+        eprintln!("Unable to read input: {}", error);
+        return ExitCode::FAILURE;
+    }
+    let (assignments, errors) = Assignments::parse_lenient(&input);
+    for error in &errors {
+        eprintln!("Invalid input at {}", error);
     }
 
-    println!("Overlap count {} (full {})", overlap_count, full_overlap_count);
+    let code = match mode {
+        Some("coverage") => print_coverage(&assignments),
+        Some("pairs") => print_pairs(&assignments),
+        _ => {
+            println!("Overlap count {} (full {})", assignments.overlap_count(), assignments.full_overlap_count());
+            ExitCode::SUCCESS
+        }
+    };
+    if errors.is_empty() { code } else { ExitCode::FAILURE }
 }
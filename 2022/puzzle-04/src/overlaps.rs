@@ -0,0 +1,84 @@
+//! Overlapping assignments across all elves.
+//!
+//! Ranges are swept by increasing lower bound, keeping the active ranges in a
+//! heap ordered by upper bound: once the ranges ending before the current one
+//! starts are dropped, every active range overlaps it. Each range enters and
+//! leaves the heap once, so `n` ranges with `k` overlapping pairs are handled
+//! in O(n log n + k).
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::range::{self, Bound, Range};
+
+/// Two ranges sharing at least one section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverlapPair {
+    /// Index of the range starting first, ties broken by index.
+    pub first: usize,
+    /// Index of the other range.
+    pub second: usize,
+    /// `true` if one of the ranges contains the other.
+    pub contained: bool
+}
+
+/// Returns every pair of overlapping ranges, ordered by the lower bound of
+/// their second range.
+pub fn overlapping_pairs<T: Bound>(ranges: &[Range<T>]) -> Vec<OverlapPair> {
+    let mut order : Vec<usize> = (0..ranges.len()).collect();
+    order.sort_by_key(|index| (ranges[*index].min(), *index));
+    let mut active : BinaryHeap<Reverse<(T, usize)>> = BinaryHeap::new();
+    let mut pairs = Vec::new();
+    for second in order {
+        let range = &ranges[second];
+        while active.peek().is_some_and(|Reverse((max, _))| *max < range.min()) {
+            active.pop();
+        }
+        pairs.extend(active.iter().map(|Reverse((_, first))| OverlapPair {
+            first: *first,
+            second,
+            contained: range::fully_overlap(&ranges[*first], range)
+        }));
+        active.push(Reverse((range.max(), second)));
+    }
+    pairs
+}
+
+#[test]
+fn test_overlapping_pairs() {
+    let ranges = [Range::new(2u8, 4), Range::new(6, 8), Range::new(3, 3), Range::new(4, 6)];
+    let mut pairs = overlapping_pairs(&ranges);
+    pairs.sort_by_key(|pair| (pair.first, pair.second));
+    assert_eq!(vec![
+        OverlapPair { first: 0, second: 2, contained: true },
+        OverlapPair { first: 0, second: 3, contained: false },
+        OverlapPair { first: 3, second: 1, contained: false }],
+        pairs);
+}
+
+#[cfg(test)]
+mod properties {
+    use super::overlapping_pairs;
+    use crate::range::{self, Range};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn matches_all_pairs_comparison(bounds in prop::collection::vec((0..100u32, 0..20u32), 0..60)) {
+            let ranges : Vec<Range<u32>> = bounds.iter().map(|(min, len)| Range::new(*min, min + len)).collect();
+            let mut found : Vec<(usize, usize, bool)> = overlapping_pairs(&ranges).iter()
+                .map(|pair| (pair.first.min(pair.second), pair.first.max(pair.second), pair.contained))
+                .collect();
+            found.sort();
+            let mut expected = Vec::new();
+            for first in 0..ranges.len() {
+                for second in first + 1..ranges.len() {
+                    if range::overlap(&ranges[first], &ranges[second]) {
+                        expected.push((first, second, range::fully_overlap(&ranges[first], &ranges[second])));
+                    }
+                }
+            }
+            prop_assert_eq!(expected, found);
+        }
+    }
+}
@@ -1,48 +1,277 @@
-use std::{fmt::Display};
+//! Inclusive ranges of section IDs.
 
-#[derive(Debug)]
-pub struct Range {
-    min : u8,
-    max : u8
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::str::FromStr;
+
+/// Integer type usable as a range bound.
+pub trait Bound: Copy + Ord + Debug + Display + FromStr {
+    /// Returns the next integer, if any.
+    fn checked_succ(self) -> Option<Self>;
+
+    /// Returns the previous integer, if any.
+    fn checked_pred(self) -> Option<Self>;
+
+    /// Converts to `i128`, which holds every bound value.
+    fn to_i128(self) -> i128;
+}
+
+macro_rules! impl_bound {
+    ($($t:ty),*) => {
+        $(impl Bound for $t {
+            fn checked_succ(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn checked_pred(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn to_i128(self) -> i128 {
+                self as i128
+            }
+        })*
+    };
 }
 
-impl Range {
+impl_bound!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
 
-    pub fn min(&self) -> u8 {
+/// An inclusive range of section IDs, never empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Range<T: Bound> {
+    min : T,
+    max : T
+}
+
+impl<T: Bound> Range<T> {
+
+    /// Creates the range from `min` to `max`, both included.
+    ///
+    /// Panics if `min` is greater than `max`.
+    pub fn new(min: T, max: T) -> Range<T> {
+        assert!(min <= max, "Empty range {}-{}", min, max);
+        Range { min, max }
+    }
+
+    /// Returns the lower bound.
+    pub fn min(&self) -> T {
         self.min
     }
 
-    pub fn max(&self) -> u8 {
+    /// Returns the upper bound.
+    pub fn max(&self) -> T {
         self.max
     }
 
-    pub fn contains(&self, other: &Range) -> bool {
+    /// Returns the count of sections.
+    pub fn len(&self) -> u128 {
+        (self.max.to_i128() - self.min.to_i128()) as u128 + 1
+    }
+
+    /// Returns `false`, as a range holds at least one section.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if `other` is fully included in this range.
+    pub fn contains(&self, other: &Range<T>) -> bool {
         self.min <= other.min && self.max >= other.max
     }
 
-    pub fn has(&self, val: &u8) -> bool {
-        self.min >= *val && *val <= self.max
+    /// Returns `true` if `val` belongs to this range.
+    pub fn has(&self, val: &T) -> bool {
+        self.min <= *val && *val <= self.max
+    }
+
+    /// Returns `true` if ranges share at least one section.
+    pub fn overlaps(&self, other: &Range<T>) -> bool {
+        self.min <= other.max && other.min <= self.max
+    }
+
+    /// Returns `true` if ranges share no section but follow each other.
+    pub fn is_adjacent(&self, other: &Range<T>) -> bool {
+        self.max.checked_succ() == Some(other.min) || other.max.checked_succ() == Some(self.min)
+    }
+
+    /// Returns the sections found in both ranges.
+    pub fn intersection(&self, other: &Range<T>) -> Option<Range<T>> {
+        self.overlaps(other)
+            .then(|| Range::new(self.min.max(other.min), self.max.min(other.max)))
+    }
+
+    /// Returns the sections found in any range, when they make a single
+    /// range: the ranges overlap or are adjacent.
+    pub fn union(&self, other: &Range<T>) -> Option<Range<T>> {
+        (self.overlaps(other) || self.is_adjacent(other))
+            .then(|| Range::new(self.min.min(other.min), self.max.max(other.max)))
+    }
+
+    /// Returns the sections of this range not found in `other`, as up to two
+    /// ranges in increasing order.
+    pub fn difference(&self, other: &Range<T>) -> Vec<Range<T>> {
+        if !self.overlaps(other) {
+            return vec![*self];
+        }
+        let below = other.min.checked_pred()
+            .filter(|max| self.min <= *max)
+            .map(|max| Range::new(self.min, max));
+        let above = other.max.checked_succ()
+            .filter(|min| *min <= self.max)
+            .map(|min| Range::new(min, self.max));
+        below.into_iter().chain(above).collect()
     }
 }
 
-pub fn fully_overlap(left: &Range, right: &Range) -> bool {
+/// Returns `true` if one of the ranges contains the other.
+pub fn fully_overlap<T: Bound>(left: &Range<T>, right: &Range<T>) -> bool {
     left.contains(right) || right.contains(left)
 }
 
-pub fn overlap(left: &Range, right: &Range) -> bool {
-    !(left.max < right.min || right.max < left.min)
+/// Returns `true` if ranges share at least one section.
+pub fn overlap<T: Bound>(left: &Range<T>, right: &Range<T>) -> bool {
+    left.overlaps(right)
+}
+
+/// Reason why a range cannot be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeError {
+    /// The bounds are not separated by a dash.
+    NoDash,
+    /// The lower bound is not an integer of the bound type.
+    InvalidLowerBound,
+    /// The upper bound is not an integer of the bound type.
+    InvalidUpperBound,
+    /// The lower bound is greater than the upper bound.
+    Decreasing
 }
 
-pub fn from_str(string : &str) -> Range {
-    let (min, max) = string.split_once("-").expect("No dash?");
-    Range {
-        min: u8::from_str_radix(min, 10).expect("Parse error"),
-        max: u8::from_str_radix(max, 10).expect("Parse error")
+impl Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RangeError::NoDash => "No dash",
+            RangeError::InvalidLowerBound => "Invalid lower bound",
+            RangeError::InvalidUpperBound => "Invalid upper bound",
+            RangeError::Decreasing => "Lower bound above upper bound"
+        })
     }
 }
 
-impl Display for Range {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Error for RangeError {}
+
+/// Parses a range written as `min-max`.
+impl<T: Bound> FromStr for Range<T> {
+    type Err = RangeError;
+
+    fn from_str(string: &str) -> Result<Range<T>, RangeError> {
+        let (min, max) = string.split_once('-').ok_or(RangeError::NoDash)?;
+        let min = min.parse::<T>().map_err(|_| RangeError::InvalidLowerBound)?;
+        let max = max.parse::<T>().map_err(|_| RangeError::InvalidUpperBound)?;
+        if min > max {
+            return Err(RangeError::Decreasing);
+        }
+        Ok(Range::new(min, max))
+    }
+}
+
+impl<T: Bound> Display for Range<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_fmt(format_args!("{}-{}", self.min, self.max))
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_operations() {
+    let range = Range::new(3u8, 7);
+    assert!(range.has(&3) && range.has(&7) && !range.has(&2) && !range.has(&8));
+    assert_eq!(5, range.len());
+    assert_eq!(Some(Range::new(5, 7)), range.intersection(&Range::new(5, 9)));
+    assert_eq!(None, range.intersection(&Range::new(8, 9)));
+    assert_eq!(Some(Range::new(3, 9)), range.union(&Range::new(8, 9)));
+    assert_eq!(None, range.union(&Range::new(9, 9)));
+    assert_eq!(vec![Range::new(3, 4), Range::new(7, 7)], range.difference(&Range::new(5, 6)));
+    assert!(range.difference(&Range::new(0, 9)).is_empty());
+    assert!(range.is_adjacent(&Range::new(0, 2)));
+}
+
+#[test]
+fn test_from_str() {
+    assert_eq!(Ok(Range::new(3u32, 700)), "3-700".parse());
+    assert_eq!(Err(RangeError::NoDash), "3_7".parse::<Range<u32>>());
+    assert_eq!(Err(RangeError::InvalidLowerBound), "-7".parse::<Range<u32>>());
+    assert_eq!(Err(RangeError::InvalidUpperBound), "3-256".parse::<Range<u8>>());
+    assert_eq!(Err(RangeError::Decreasing), "7-3".parse::<Range<u32>>());
+}
+
+#[test]
+fn test_bounds_limits() {
+    let range = Range::new(i64::MIN, i64::MAX);
+    assert_eq!(1u128 << 64, range.len());
+    assert_eq!(vec![Range::new(i64::MIN, -1)], range.difference(&Range::new(0, i64::MAX)));
+    assert!(!Range::new(0u8, 255).is_adjacent(&Range::new(0, 0)));
+}
+
+#[cfg(test)]
+mod properties {
+    use super::Range;
+    use proptest::prelude::*;
+
+    fn range() -> impl Strategy<Value = Range<u8>> {
+        (any::<u8>(), any::<u8>()).prop_map(|(a, b)| Range::new(a.min(b), a.max(b)))
+    }
+
+    fn has_any(ranges: &[Range<u8>], value: u8) -> bool {
+        ranges.iter().any(|range| range.has(&value))
+    }
+
+    proptest! {
+        #[test]
+        fn has_matches_bounds(range in range(), value in any::<u8>()) {
+            prop_assert_eq!(range.min() <= value && value <= range.max(), range.has(&value));
+        }
+
+        #[test]
+        fn len_counts_sections(range in range()) {
+            prop_assert_eq!(range.len(), (0..=255u8).filter(|v| range.has(v)).count() as u128);
+        }
+
+        #[test]
+        fn intersection_is_common_sections(a in range(), b in range()) {
+            let intersection = a.intersection(&b);
+            prop_assert_eq!(a.overlaps(&b), intersection.is_some());
+            for value in 0..=255u8 {
+                prop_assert_eq!(a.has(&value) && b.has(&value), intersection.is_some_and(|i| i.has(&value)));
+            }
+        }
+
+        #[test]
+        fn union_is_all_sections(a in range(), b in range()) {
+            match a.union(&b) {
+                Some(union) => for value in 0..=255u8 {
+                    prop_assert_eq!(a.has(&value) || b.has(&value), union.has(&value));
+                },
+                None => prop_assert!(!a.overlaps(&b) && !a.is_adjacent(&b))
+            }
+        }
+
+        #[test]
+        fn difference_is_own_sections(a in range(), b in range()) {
+            let difference = a.difference(&b);
+            prop_assert!(difference.len() <= 2);
+            prop_assert!(difference.windows(2).all(|pair| pair[0].max() < pair[1].min()));
+            for value in 0..=255u8 {
+                prop_assert_eq!(a.has(&value) && !b.has(&value), has_any(&difference, value));
+            }
+        }
+
+        #[test]
+        fn adjacency_is_touching_without_overlap(a in range(), b in range()) {
+            prop_assert_eq!(a.is_adjacent(&b), !a.overlaps(&b) && a.union(&b).is_some());
+            prop_assert_eq!(a.is_adjacent(&b), b.is_adjacent(&a));
+        }
+
+        #[test]
+        fn contains_is_full_intersection(a in range(), b in range()) {
+            prop_assert_eq!(a.contains(&b), a.intersection(&b) == Some(b));
+        }
+    }
+}
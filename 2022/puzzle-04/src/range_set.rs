@@ -0,0 +1,204 @@
+//! Sets of sections, stored as disjoint ranges.
+
+use std::fmt::{self, Display};
+
+use crate::range::{Bound, Range};
+
+/// A set of sections, as ranges in increasing order that neither overlap
+/// nor follow each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeSet<T: Bound> {
+    ranges: Vec<Range<T>>
+}
+
+impl<T: Bound> Default for RangeSet<T> {
+    fn default() -> Self {
+        RangeSet { ranges: Vec::new() }
+    }
+}
+
+impl<T: Bound> RangeSet<T> {
+
+    /// Creates an empty set.
+    pub fn new() -> RangeSet<T> {
+        RangeSet::default()
+    }
+
+    /// Returns the normalised ranges.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    /// Returns the count of sections.
+    pub fn len(&self) -> u128 {
+        self.ranges.iter().map(Range::len).sum()
+    }
+
+    /// Returns `true` if the set has no section.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the lowest and highest sections, as a range.
+    pub fn span(&self) -> Option<Range<T>> {
+        Some(Range::new(self.ranges.first()?.min(), self.ranges.last()?.max()))
+    }
+
+    /// Adds the sections of `range`, merging it with the ranges it overlaps
+    /// or follows.
+    pub fn insert(&mut self, range: Range<T>) {
+        let start = self.ranges.partition_point(|r| r.max() < range.min() && !r.is_adjacent(&range));
+        let end = self.ranges.partition_point(|r| r.min() <= range.max() || r.is_adjacent(&range));
+        let merged = self.ranges[start..end].iter()
+            .fold(range, |merged, r| merged.union(r).expect("Touching ranges"));
+        self.ranges.splice(start..end, [merged]);
+    }
+
+    /// Returns `true` if the set holds the section `val`.
+    pub fn has(&self, val: &T) -> bool {
+        let index = self.ranges.partition_point(|r| r.max() < *val);
+        self.ranges.get(index).is_some_and(|r| r.has(val))
+    }
+
+    /// Returns `true` if the set holds every section of `range`.
+    pub fn covers(&self, range: &Range<T>) -> bool {
+        let index = self.ranges.partition_point(|r| r.max() < range.min());
+        self.ranges.get(index).is_some_and(|r| r.contains(range))
+    }
+
+    /// Returns the sections of `within` missing from the set, as ranges in
+    /// increasing order.
+    pub fn gaps(&self, within: &Range<T>) -> Vec<Range<T>> {
+        let mut gaps = vec![*within];
+        for range in self.ranges.iter().filter(|r| r.overlaps(within)) {
+            let last = gaps.pop().expect("Remaining sections");
+            gaps.extend(last.difference(range));
+        }
+        gaps
+    }
+}
+
+impl<T: Bound> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(ranges: I) -> RangeSet<T> {
+        let mut sorted : Vec<Range<T>> = ranges.into_iter().collect();
+        sorted.sort_by_key(|r| r.min());
+        let mut merged : Vec<Range<T>> = Vec::with_capacity(sorted.len());
+        for range in sorted {
+            match merged.last_mut().and_then(|last| last.union(&range).map(|union| (last, union))) {
+                Some((last, union)) => *last = union,
+                None => merged.push(range)
+            }
+        }
+        RangeSet { ranges: merged }
+    }
+}
+
+impl<T: Bound> Display for RangeSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ranges : Vec<String> = self.ranges.iter().map(Range::to_string).collect();
+        write!(f, "{}", ranges.join(", "))
+    }
+}
+
+/// Returns the count of ranges holding each section, as the ranges of
+/// sections sharing the same non-zero count, in increasing order.
+pub fn depths<T: Bound>(ranges: &[Range<T>]) -> Vec<(Range<T>, usize)> {
+    let mut mins : Vec<T> = ranges.iter().map(Range::min).collect();
+    let mut maxes : Vec<T> = ranges.iter().map(Range::max).collect();
+    mins.sort();
+    maxes.sort();
+    let mut boundaries : Vec<T> = mins.iter().copied()
+        .chain(maxes.iter().filter_map(|max| max.checked_succ()))
+        .collect();
+    boundaries.sort();
+    boundaries.dedup();
+
+    let depth_at = |section: T| mins.partition_point(|min| *min <= section)
+        - maxes.partition_point(|max| *max < section);
+    let mut segments : Vec<(Range<T>, usize)> = Vec::new();
+    for (index, start) in boundaries.iter().enumerate() {
+        let depth = depth_at(*start);
+        if depth == 0 {
+            continue;
+        }
+        let end = match boundaries.get(index + 1) {
+            Some(next) => next.checked_pred().expect("Boundary above a section"),
+            None => *maxes.last().expect("Ranges")
+        };
+        segments.push((Range::new(*start, end), depth));
+    }
+    segments
+}
+
+/// Coverage of the sections by all assignments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coverage<T: Bound> {
+    /// Sections assigned to at least one elf.
+    pub covered: RangeSet<T>,
+    /// Sections between the lowest and the highest assigned ones, assigned
+    /// to no elf.
+    pub uncovered: Vec<Range<T>>,
+    /// Sections assigned to more than one elf.
+    pub multiple: RangeSet<T>,
+    /// Highest count of elves assigned to a same section.
+    pub max_depth: usize,
+    /// Sections reaching the highest count.
+    pub deepest: RangeSet<T>
+}
+
+/// Returns the coverage of the sections by `ranges`, or `None` when there
+/// is no range.
+pub fn coverage<T: Bound>(ranges: &[Range<T>]) -> Option<Coverage<T>> {
+    let covered : RangeSet<T> = ranges.iter().copied().collect();
+    let uncovered = covered.gaps(&covered.span()?);
+    let depths = depths(ranges);
+    let max_depth = depths.iter().map(|(_, depth)| *depth).max()?;
+    let deeper_than = |threshold: usize| depths.iter()
+        .filter(|(_, depth)| *depth > threshold)
+        .map(|(range, _)| *range)
+        .collect::<RangeSet<T>>();
+    Some(Coverage { covered, uncovered, multiple: deeper_than(1), max_depth, deepest: deeper_than(max_depth - 1) })
+}
+
+#[test]
+fn test_insert() {
+    let mut set = RangeSet::new();
+    set.insert(Range::new(10u8, 12));
+    set.insert(Range::new(1, 2));
+    set.insert(Range::new(5, 6));
+    assert_eq!("1-2, 5-6, 10-12", set.to_string());
+    set.insert(Range::new(3, 4));
+    assert_eq!("1-6, 10-12", set.to_string());
+    set.insert(Range::new(0, 255));
+    assert_eq!("0-255", set.to_string());
+    assert_eq!(256, set.len());
+}
+
+#[test]
+fn test_queries() {
+    let set : RangeSet<u32> = [Range::new(7, 9), Range::new(2, 4), Range::new(3, 5)].into_iter().collect();
+    assert_eq!(&[Range::new(2, 5), Range::new(7, 9)], set.ranges());
+    assert!(set.has(&5) && !set.has(&6) && !set.has(&10));
+    assert!(set.covers(&Range::new(3, 5)) && !set.covers(&Range::new(5, 7)));
+    assert_eq!(vec![Range::new(1, 1), Range::new(6, 6), Range::new(10, 12)], set.gaps(&Range::new(1, 12)));
+    assert_eq!(set, {
+        let mut inserted = RangeSet::new();
+        [Range::new(7, 9), Range::new(2, 4), Range::new(3, 5)].into_iter().for_each(|r| inserted.insert(r));
+        inserted
+    });
+}
+
+#[test]
+fn test_coverage() {
+    let ranges = [Range::new(2u8, 4), Range::new(6, 8), Range::new(3, 7), Range::new(4, 4), Range::new(12, 255)];
+    assert_eq!(vec![
+        (Range::new(2, 2), 1), (Range::new(3, 3), 2), (Range::new(4, 4), 3), (Range::new(5, 5), 1),
+        (Range::new(6, 7), 2), (Range::new(8, 8), 1), (Range::new(12, 255), 1)],
+        depths(&ranges));
+    assert_eq!(None, coverage::<u8>(&[]));
+    let report = coverage(&ranges).expect("Ranges");
+    assert_eq!("2-8, 12-255", report.covered.to_string());
+    assert_eq!(vec![Range::new(9, 11)], report.uncovered);
+    assert_eq!("3-4, 6-7", report.multiple.to_string());
+    assert_eq!((3, "4-4".to_string()), (report.max_depth, report.deepest.to_string()));
+}
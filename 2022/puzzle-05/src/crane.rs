@@ -0,0 +1,186 @@
+//! Crane models, each moving crates its own way.
+//!
+//! The stacks are the ones of [`Supplies`](crate::Supplies): the top crate of
+//! each stack comes first. Cranes expect valid move orders, naming existing
+//! stacks and moving no more crates than the source holds. A move within a
+//! stack may change it, so [`Supplies::replay`](crate::Supplies::replay)
+//! does not apply those.
+
+use std::collections::VecDeque;
+
+use crate::MoveOrder;
+
+/// Moves crates between stacks.
+pub trait Crane {
+    /// Returns the name selecting the crane.
+    fn name(&self) -> String;
+
+    /// Applies a move order to `stacks`.
+    fn apply(&self, stacks: &mut [VecDeque<String>], move_order: MoveOrder);
+}
+
+/// Takes the `quantity` top crates of a stack, the top one first.
+fn lift_top(stack: &mut VecDeque<String>, quantity: usize) -> Vec<String> {
+    assert!(quantity <= stack.len(), "No item?");
+    stack.drain(..quantity).collect()
+}
+
+/// Puts crates on top of a stack, `block[0]` ending at the top.
+fn put_block(stack: &mut VecDeque<String>, block: Vec<String>) {
+    for item in block.into_iter().rev() {
+        stack.push_front(item);
+    }
+}
+
+/// Returns the quantity and the 0-based source and target of a move order.
+fn indices(move_order: MoveOrder) -> (usize, usize, usize) {
+    let (quantity, source, target) = move_order;
+    (quantity as usize, source as usize - 1, target as usize - 1)
+}
+
+/// The CrateMover 9000, moving crates one at a time.
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        String::from("9000")
+    }
+
+    fn apply(&self, stacks: &mut [VecDeque<String>], move_order: MoveOrder) {
+        let (quantity, source, target) = indices(move_order);
+        for _ in 0..quantity {
+            let taken = stacks[source].pop_front().expect("No item?");
+            stacks[target].push_front(taken);
+        }
+    }
+}
+
+/// The CrateMover 9001, moving crates all at once so that they keep their
+/// order.
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        String::from("9001")
+    }
+
+    fn apply(&self, stacks: &mut [VecDeque<String>], move_order: MoveOrder) {
+        let (quantity, source, target) = indices(move_order);
+        let block = lift_top(&mut stacks[source], quantity);
+        put_block(&mut stacks[target], block);
+    }
+}
+
+/// A crane moving blocks like the CrateMover 9001, but lifting at most
+/// `capacity` crates at a time: larger moves are split into several lifts.
+pub struct LimitedCapacity {
+    capacity: usize
+}
+
+impl LimitedCapacity {
+    /// Creates a crane lifting up to `capacity` crates, which must not be 0.
+    pub fn new(capacity: usize) -> LimitedCapacity {
+        assert!(capacity > 0, "No capacity");
+        LimitedCapacity { capacity }
+    }
+}
+
+impl Crane for LimitedCapacity {
+    fn name(&self) -> String {
+        format!("limited:{}", self.capacity)
+    }
+
+    fn apply(&self, stacks: &mut [VecDeque<String>], move_order: MoveOrder) {
+        let (quantity, source, target) = indices(move_order);
+        let mut remaining = quantity;
+        while remaining > 0 {
+            let lift = remaining.min(self.capacity);
+            let block = lift_top(&mut stacks[source], lift);
+            put_block(&mut stacks[target], block);
+            remaining -= lift;
+        }
+    }
+}
+
+/// A crane moving crates all at once, but turning the block upside down.
+/// Stacks end like with the CrateMover 9000, in a single lift.
+pub struct ReversedBlock;
+
+impl Crane for ReversedBlock {
+    fn name(&self) -> String {
+        String::from("reversed")
+    }
+
+    fn apply(&self, stacks: &mut [VecDeque<String>], move_order: MoveOrder) {
+        let (quantity, source, target) = indices(move_order);
+        let mut block = lift_top(&mut stacks[source], quantity);
+        block.reverse();
+        put_block(&mut stacks[target], block);
+    }
+}
+
+/// A crane pulling crates all at once from the bottom of the source stack
+/// and putting them, in the same order, on top of the target stack.
+pub struct FromBottom;
+
+impl Crane for FromBottom {
+    fn name(&self) -> String {
+        String::from("bottom")
+    }
+
+    fn apply(&self, stacks: &mut [VecDeque<String>], move_order: MoveOrder) {
+        let (quantity, source, target) = indices(move_order);
+        let stack = &mut stacks[source];
+        assert!(quantity <= stack.len(), "No item?");
+        let block : Vec<String> = stack.split_off(stack.len() - quantity).into();
+        put_block(&mut stacks[target], block);
+    }
+}
+
+/// Names of the cranes accepted by [`crane`], `N` being a lift capacity.
+pub const CRANE_NAMES : [&str; 5] = ["9000", "9001", "limited:N", "reversed", "bottom"];
+
+/// Returns the crane of a name, as listed in [`CRANE_NAMES`].
+pub fn crane(name: &str) -> Option<Box<dyn Crane>> {
+    match name {
+        "9000" => Some(Box::new(CrateMover9000)),
+        "9001" => Some(Box::new(CrateMover9001)),
+        "reversed" => Some(Box::new(ReversedBlock)),
+        "bottom" => Some(Box::new(FromBottom)),
+        _ => {
+            let capacity = name.strip_prefix("limited:")?.parse::<usize>().ok().filter(|c| *c > 0)?;
+            Some(Box::new(LimitedCapacity::new(capacity)))
+        }
+    }
+}
+
+#[cfg(test)]
+fn stacks_of(stacks: &[&str]) -> Vec<VecDeque<String>> {
+    stacks.iter().map(|stack| stack.chars().map(String::from).collect()).collect()
+}
+
+#[cfg(test)]
+fn moved(crane: &dyn Crane, move_order: MoveOrder) -> Vec<String> {
+    let mut stacks = stacks_of(&["ABCD", "E"]);
+    crane.apply(&mut stacks, move_order);
+    stacks.iter().map(|stack| stack.iter().cloned().collect()).collect()
+}
+
+#[test]
+fn test_cranes() {
+    assert_eq!(vec!["D", "CBAE"], moved(&CrateMover9000, (3, 1, 2)));
+    assert_eq!(vec!["D", "ABCE"], moved(&CrateMover9001, (3, 1, 2)));
+    assert_eq!(vec!["D", "CABE"], moved(&LimitedCapacity::new(2), (3, 1, 2)));
+    assert_eq!(vec!["D", "CBAE"], moved(&ReversedBlock, (3, 1, 2)));
+    assert_eq!(vec!["A", "BCDE"], moved(&FromBottom, (3, 1, 2)));
+    assert_eq!(vec!["DABC", "E"], moved(&FromBottom, (1, 1, 1)));
+}
+
+#[test]
+fn test_crane_names() {
+    for name in ["9000", "9001", "limited:3", "reversed", "bottom"] {
+        assert_eq!(Some(name.to_string()), crane(name).map(|crane| crane.name()));
+    }
+    assert!(crane("limited:0").is_none());
+    assert!(crane("9002").is_none());
+}
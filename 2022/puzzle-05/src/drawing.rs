@@ -0,0 +1,103 @@
+//! Stack states, as drawings found at the start of the puzzle input or as
+//! JSON. Both are read back into the same stacks.
+
+use std::collections::VecDeque;
+
+use aoc_common::ParseError;
+
+use crate::{parse_stack_symbol, parse_supplies, MoveOrder};
+
+/// Renders stacks as a drawing: crates as `[X]` in columns 4 characters
+/// apart, the top crates on the first line, then the label row.
+pub fn render(stacks: &[VecDeque<String>]) -> String {
+    let height = stacks.iter().map(VecDeque::len).max().unwrap_or(0);
+    let width = (stacks.len() * 4).saturating_sub(1);
+    let mut drawing = String::new();
+    for level in (0..height).rev() {
+        let cells : Vec<String> = stacks.iter()
+            .map(|stack| match stack.len().checked_sub(level + 1).map(|index| &stack[index]) {
+                Some(item) => format!("[{}]", item),
+                None => String::from("   ")
+            })
+            .collect();
+        drawing.push_str(&cells.join(" "));
+        drawing.push('\n');
+    }
+    let labels : String = (1..=stacks.len()).map(|label| format!(" {:<3}", label)).collect();
+    drawing.push_str(&labels[..width]);
+    drawing.push('\n');
+    drawing
+}
+
+/// Reads the stacks of a drawing, ignoring any move order after it.
+pub fn parse_drawing(input: &str) -> Result<Vec<VecDeque<String>>, ParseError> {
+    Ok(parse_supplies(input)?.stacks)
+}
+
+/// Renders stacks as a JSON array holding the crates of each stack, from the
+/// bottom one to the top one.
+pub fn to_json(stacks: &[VecDeque<String>]) -> String {
+    let stacks : Vec<Vec<&String>> = stacks.iter().map(|stack| stack.iter().rev().collect()).collect();
+    serde_json::to_string_pretty(&stacks).expect("Serializable stacks")
+}
+
+/// Returns the line and column of a byte offset in `text`.
+fn position(text: &str, offset: usize) -> (usize, usize) {
+    let line_start = text[..offset].rfind('\n').map_or(0, |index| index + 1);
+    (text[..offset].matches('\n').count() + 1, offset - line_start + 1)
+}
+
+/// Reads stacks written by [`to_json`].
+pub fn from_json(json: &str) -> Result<Vec<VecDeque<String>>, ParseError> {
+    let stacks : Vec<Vec<String>> = serde_json::from_str(json)
+        .map_err(|error| ParseError::new(error.line(), error.column(), "", &error.to_string()))?;
+    for name in stacks.iter().flatten() {
+        if name.len() != 1 || parse_stack_symbol(name).is_none() {
+            let (line, column) = position(json, json.find(&format!("\"{}\"", name)).unwrap_or(0));
+            return Err(ParseError::new(line, column, name, "Invalid crate name"));
+        }
+    }
+    Ok(stacks.into_iter().map(|stack| stack.into_iter().rev().collect()).collect())
+}
+
+/// Renders a move order as written in the puzzle input.
+pub fn render_move(move_order: MoveOrder) -> String {
+    let (quantity, source, target) = move_order;
+    format!("move {} from {} to {}", quantity, source, target)
+}
+
+#[test]
+fn test_render() {
+    let input = std::fs::read_to_string("test.log").expect("Test file");
+    let supplies = crate::read_supplies(&input).expect("Valid input");
+    let drawing = render(&supplies.stacks);
+    assert!(input.starts_with(&drawing));
+    assert_eq!(supplies.stacks, crate::read_supplies(&drawing).expect("Valid drawing").stacks);
+    assert_eq!("move 3 from 1 to 12", render_move((3, 1, 12)));
+}
+
+#[test]
+fn test_round_trips() {
+    let supplies = parse_supplies(&std::fs::read_to_string("test.log").expect("Test file")).expect("Valid input");
+    let stacks = supplies.replay(&crate::crane::CrateMover9001, |_, _| ());
+    assert_eq!(stacks, parse_drawing(&render(&stacks)).expect("Valid drawing"));
+    assert_eq!(stacks, from_json(&to_json(&stacks)).expect("Valid JSON"));
+    assert_eq!(
+        vec![vec!["M"], vec!["C"], vec!["P", "Z", "N", "D"]],
+        serde_json::from_str::<Vec<Vec<String>>>(&to_json(&stacks)).expect("Valid JSON"));
+}
+
+#[test]
+fn test_invalid_json() {
+    let error = from_json("[[\"A\"],\n [\"B\", \"cd\"]]").expect_err("Invalid crate");
+    assert_eq!((2, 8, "cd"), (error.line, error.column, error.text.as_str()));
+    let error = from_json("[[\"A\"], 3]").expect_err("Invalid stack");
+    assert_eq!(1, error.line);
+}
+
+#[test]
+fn test_render_empty() {
+    let stacks = vec![VecDeque::new(), VecDeque::from([String::from("A")])];
+    assert_eq!("    [A]\n 1   2 \n", render(&stacks));
+    assert_eq!(" 1 \n", render(&[VecDeque::new()]));
+}
@@ -0,0 +1,317 @@
+//! Day 5: Supply Stacks
+//!
+//! Rearranges stacks of crates according to a list of move orders.
+
+#![warn(missing_docs)]
+
+use std::fs;
+use std::path::Path;
+use std::collections::VecDeque;
+
+use aoc_common::{ParseError, Solution};
+
+pub mod crane;
+pub mod drawing;
+pub mod validate;
+use crane::{Crane, CrateMover9000, CrateMover9001};
+use validate::MoveError;
+
+/// Returns the symbol if it is a crate name (an uppercase letter).
+pub fn parse_stack_symbol(symbol: &str) -> Option<&str> {
+    let val = symbol.as_bytes()[0];
+    if val.is_ascii_uppercase() { Some(symbol) } else { None }
+}
+
+/// Parses the label row of the drawing, ` 1   2   3`, into the column of each
+/// stack. Labels must count stacks from 1.
+pub fn parse_label_row(line_number: usize, line: &str) -> Result<Vec<usize>, ParseError> {
+    let mut columns = Vec::new();
+    let mut offset = 0;
+    for label in line.split_ascii_whitespace() {
+        let column = offset + line[offset..].find(label).expect("Label of the line");
+        offset = column + label.len();
+        if label.parse::<usize>().ok() != Some(columns.len() + 1) {
+            return Err(ParseError::new(line_number, column + 1, label, "Unexpected stack label"));
+        }
+        columns.push(column);
+    }
+    if columns.is_empty() {
+        return Err(ParseError::new(line_number, 1, line, "Missing stack labels"));
+    }
+    Ok(columns)
+}
+
+/// Tells whether a drawing line is the label row, made of numbers only.
+pub fn is_label_row(line: &str) -> bool {
+    !line.trim().is_empty() && line.split_ascii_whitespace().all(|token| token.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Parses a line of the drawing into the crate of each stack, if any, the
+/// crate names being read at the `columns` of the label row. Trailing spaces
+/// may be missing. Anything but spaces outside of the labelled columns and
+/// their brackets is rejected.
+pub fn parse_stack_info(line_number: usize, line : &str, columns: &[usize]) -> Result<Vec<Option<String>>, ParseError> {
+    let labelled = |position: usize, c: char| match c {
+        ' ' => true,
+        '[' => columns.contains(&(position + 1)),
+        ']' => position > 0 && columns.contains(&(position - 1)),
+        _ => columns.contains(&position)
+    };
+    if let Some((position, c)) = line.char_indices().find(|(position, c)| !labelled(*position, *c)) {
+        // Points at the crate name rather than at its opening bracket
+        let (position, c) = match (c, line[position + c.len_utf8()..].chars().next()) {
+            ('[', Some(name)) if name != ' ' && name != ']' => (position + 1, name),
+            _ => (position, c)
+        };
+        return Err(ParseError::new(line_number, position + 1, &c.to_string(), "Crate without stack label"));
+    }
+    columns.iter()
+        .map(|column| match line.get(*column..*column + 1) {
+            None | Some(" ") => Ok(None),
+            Some(symbol) => parse_stack_symbol(symbol)
+                .map(|name| Some(name.to_string()))
+                .ok_or_else(|| ParseError::new(line_number, column + 1, symbol, "Invalid crate name"))
+        })
+        .collect()
+}
+
+/// Puts the crates of a drawing line under the existing crates of each stack.
+pub fn process_stack_info(stacks: &mut [VecDeque<String>], input: &[Option<String>]) {
+    for (index, stack) in input.iter().enumerate() {
+        match stack {
+            None => (),
+            Some(item) => stacks[index].push_back(item.clone())
+        }
+    }
+}
+
+/// Parses a move order, written as `move q from f to t`.
+pub fn parse_move_order(line_number: usize, line: &str) -> Result<MoveOrder, ParseError> {
+    let tokens : Vec<&str> = line.split_ascii_whitespace().collect();
+    if tokens.len() != 6 {
+        return Err(ParseError::new(line_number, 1, line, "Expected `move q from f to t`"));
+    }
+    let keyword = |index: usize, expected: &str| {
+        if tokens[index] == expected { Ok(()) }
+        else { Err(ParseError::in_line(line_number, line, tokens[index], &format!("Expected `{}`", expected))) }
+    };
+    let number = |index: usize| tokens[index].parse::<u8>()
+        .map_err(|_| ParseError::in_line(line_number, line, tokens[index], "Invalid number"));
+    keyword(0, "move")?;
+    keyword(2, "from")?;
+    keyword(4, "to")?;
+    Ok((number(1)?, number(3)?, number(5)?))
+}
+
+#[test]
+fn test_move_parsing() {
+    let (quantity, from, to) = parse_move_order(1, "move 4 from 3 to 2").expect("Valid move");
+    assert_eq!(quantity, 4);
+    assert_eq!(from,     3);
+    assert_eq!(to,       2);
+}
+
+/// Moves crates from a stack to another.
+///
+/// Crates are moved one at a time unless `mutiple` is `true`, in which case
+/// they are moved all at once and keep their order.
+pub fn move_items(stacks: &mut [VecDeque<String>], move_order: MoveOrder, mutiple: bool) {
+    if mutiple {
+        CrateMover9001.apply(stacks, move_order);
+    }
+    else {
+        CrateMover9000.apply(stacks, move_order);
+    }
+}
+
+/// A move order, as (quantity, source, target).
+pub type MoveOrder = (u8, u8, u8);
+
+/// Initial stacks of crates, along with the move orders to be applied.
+#[derive(Debug)]
+pub struct Supplies {
+    /// Crates of each stack, the top crate being first.
+    pub stacks: Vec<VecDeque<String>>,
+    /// Move orders, in application order.
+    pub moves: Vec<MoveOrder>,
+    /// Line of each move order in the input.
+    pub move_lines: Vec<usize>
+}
+
+impl Supplies {
+
+    /// Applies all moves and returns the item at the top of each stack.
+    pub fn rearrange(&self, multiple: bool) -> Vec<String> {
+        if multiple {
+            self.rearrange_with(&CrateMover9001)
+        }
+        else {
+            self.rearrange_with(&CrateMover9000)
+        }
+    }
+
+    /// Applies all moves with `crane` and returns the item at the top of
+    /// each stack, empty for an empty stack.
+    pub fn rearrange_with(&self, crane: &dyn Crane) -> Vec<String> {
+        self.replay(crane, |_, _| ()).iter()
+            .map(|deq| deq.front().cloned().unwrap_or_default())
+            .collect()
+    }
+
+    /// Applies all moves with `crane`, calling `on_move` with each move order
+    /// and the stacks it leaves, then returns the final stacks. Moves within
+    /// a stack are not applied, whatever the crane.
+    pub fn replay(&self, crane: &dyn Crane, mut on_move: impl FnMut(MoveOrder, &[VecDeque<String>])) -> Vec<VecDeque<String>> {
+        let mut stacks = self.stacks.clone();
+        for move_order in &self.moves {
+            let (_, source, target) = *move_order;
+            if source != target {
+                crane.apply(&mut stacks, *move_order);
+            }
+            on_move(*move_order, &stacks);
+        }
+        stacks
+    }
+}
+
+/// Reads the drawing and move orders of a file, then rearranges the stacks.
+pub fn process(file_path: &dyn std::convert::AsRef<Path>, multiple: bool) -> Result<Vec<String>, String> {
+    let path = file_path.as_ref();
+    let input = fs::read_to_string(path)
+        .map_err(|error| format!("Unable to read {}: {}", path.display(), error))?;
+    let supplies = read_supplies(&input)
+        .map_err(|error| format!("Invalid input {} at {}", path.display(), error))?;
+    Ok(supplies.rearrange(multiple))
+}
+
+/// Reads the drawing and the move orders, rejecting the first move order
+/// that cannot be applied. Move orders moving nothing, including the moves
+/// within a stack, are kept. The stacks are found from the label row closing
+/// the drawing.
+pub fn read_supplies(input: &str) -> Result<Supplies, ParseError> {
+    let supplies = parse_supplies(input)?;
+    let (_, invalid) = validate::validate(&supplies, validate::Repair::Skip);
+    match invalid.iter().find(|invalid| invalid.error.is_fatal()) {
+        None => Ok(supplies),
+        Some(invalid) => {
+            let line_str = input.lines().nth(invalid.line - 1).expect("Move order line");
+            let tokens : Vec<&str> = line_str.split_ascii_whitespace().collect();
+            let token = match invalid.error {
+                MoveError::UnknownSource => tokens[3],
+                MoveError::UnknownTarget => tokens[5],
+                _ => tokens[1]
+            };
+            Err(ParseError::in_line(invalid.line, line_str, token, &invalid.error.to_string()))
+        }
+    }
+}
+
+/// Reads the drawing and the move orders, keeping the move orders that
+/// cannot be applied, to be checked by [`validate::validate`].
+pub fn parse_supplies(input: &str) -> Result<Supplies, ParseError> {
+
+    // Inventory
+    let mut drawing : Vec<(usize, &str)> = Vec::new();
+    let mut stacks : Option<Vec<VecDeque<String>>> = None;
+    let mut moves : Vec<MoveOrder> = Vec::new();
+    let mut move_lines : Vec<usize> = Vec::new();
+
+    // Reading
+    for (index, line_str) in input.lines().enumerate() {
+        let line_number = index + 1;
+        match stacks {
+            None if line_str.trim().is_empty() => {
+                return Err(ParseError::new(line_number, 1, line_str, "Missing stack labels"));
+            },
+            None if is_label_row(line_str) => {
+                let columns = parse_label_row(line_number, line_str)?;
+                let mut new_stacks = vec![VecDeque::new(); columns.len()];
+                for (drawing_number, drawing_line) in &drawing {
+                    let stack_line = parse_stack_info(*drawing_number, drawing_line, &columns)?;
+                    process_stack_info(&mut new_stacks, &stack_line);
+                }
+                stacks = Some(new_stacks);
+            },
+            None => drawing.push((line_number, line_str)),
+            Some(_) if line_str.is_empty() => {
+                if !moves.is_empty() {
+                    break;
+                }
+            },
+            Some(_) => {
+                moves.push(parse_move_order(line_number, line_str)?);
+                move_lines.push(line_number);
+            }
+        }
+    }
+
+    let stacks = stacks.ok_or_else(|| ParseError::new(input.lines().count(), 1, "", "Missing stack labels"))?;
+    Ok(Supplies { stacks, moves, move_lines })
+}
+
+#[test]
+fn test_example1() {
+    let result = process(&"test.log", false).expect("Valid input");
+    assert_eq!("CMZ", result.join("").as_str());
+}
+
+#[test]
+fn test_example2() {
+    let result = process(&"test.log", true).expect("Valid input");
+    assert_eq!("MCD", result.join("").as_str());
+    assert!(process(&"missing.log", true).expect_err("No file").starts_with("Unable to read missing.log"));
+}
+
+impl Solution for Supplies {
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        read_supplies(input)
+    }
+
+    fn part1(&self) -> String {
+        self.rearrange(false).join("")
+    }
+
+    fn part2(&self) -> String {
+        self.rearrange(true).join("")
+    }
+}
+
+#[test]
+fn test_invalid_move() {
+    let error = parse_move_order(12, "move 4 from x to 2").expect_err("Invalid number");
+    assert_eq!((12, 13), (error.line, error.column));
+    let error = read_supplies("[A] [B]\n 1   2 \n\nmove 1 from 1 to 3\n").expect_err("Unknown stack");
+    assert_eq!((4, 18), (error.line, error.column));
+    let error = read_supplies("[A] [B]\n 1   2 \n\nmove 1 from 1 to 2\nmove 2 from 1 to 2\n").expect_err("Underflow");
+    assert_eq!((5, 6, "Only 0 crates in the source stack"), (error.line, error.column, error.message.as_str()));
+    let supplies = read_supplies("[A] [B]\n[C]\n[D]\n 1   2 \n\nmove 0 from 1 to 2\nmove 2 from 1 to 1\n").expect("Moves of nothing");
+    assert_eq!(vec!["A", "B"], supplies.rearrange(true));
+    for crane in [&crane::FromBottom as &dyn Crane, &crane::ReversedBlock] {
+        assert_eq!(supplies.stacks, supplies.replay(crane, |_, _| ()));
+    }
+}
+
+#[test]
+fn test_trimmed_drawing() {
+    let supplies = read_supplies("    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n\nmove 1 from 2 to 1\n").expect("Valid input");
+    assert_eq!(3, supplies.stacks.len());
+    assert_eq!(vec!["D", "C", "M"], supplies.stacks[1].iter().collect::<Vec<_>>());
+    assert_eq!(vec![2, 6, 10], parse_label_row(4, "  1   2   3").expect("Valid labels"));
+}
+
+#[test]
+fn test_invalid_drawing() {
+    let error = parse_label_row(3, " 1   3").expect_err("Unexpected label");
+    assert_eq!((3, 6, "3"), (error.line, error.column, error.text.as_str()));
+    let error = read_supplies("[A]\n\nmove 1 from 1 to 1\n").expect_err("No labels");
+    assert_eq!((2, "Missing stack labels"), (error.line, error.message.as_str()));
+    let error = read_supplies("[a]\n 1\n\n").expect_err("Invalid crate");
+    assert_eq!((1, 2), (error.line, error.column));
+    let error = read_supplies("    [C]\n[A] [B]\n 1\n\n").expect_err("Unlabelled crate");
+    assert_eq!((1, 6, "C", "Crate without stack label"), (error.line, error.column, error.text.as_str(), error.message.as_str()));
+    let error = parse_stack_info(2, "[A]  B", &[1]).expect_err("Unlabelled crate");
+    assert_eq!((2, 6, "B"), (error.line, error.column, error.text.as_str()));
+}
@@ -1,183 +1,214 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
-use std::str::FromStr;
-use std::{env, usize};
 use std::collections::VecDeque;
-
-fn parse_stack_symbol(symbol: &str) -> Option<&str> {
-    let val = symbol.as_bytes()[0];
-    if val.is_ascii_uppercase() { Some(symbol) } else { None }
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+
+use puzzle_05::crane::{crane, Crane, CRANE_NAMES};
+use puzzle_05::drawing::{from_json, parse_drawing, render, render_move, to_json};
+use puzzle_05::validate::{validate, Repair};
+use puzzle_05::{parse_supplies, Supplies};
+
+static DEFAULT_CRANE : &str = "9001";
+static DEFAULT_DELAY : u64 = 200;
+
+/// Options of the program.
+struct Args {
+    file_path : String,
+    cranes : Vec<Box<dyn Crane>>,
+    delay : Duration,
+    frames : Option<String>,
+    repair : Option<Repair>,
+    start : Option<String>,
+    save : Option<String>
 }
 
-fn parse_stack_info(line : &String, count: usize) -> Vec<Option<String>> {
-    let mut result: Vec<Option<String>> = vec![Option::None; count];
-    for stack in 0..count {
-        let part_index = stack * 4;
-        let opt_value = parse_stack_symbol(&line[part_index+1..part_index+2]);
-        match opt_value {
-            Some(v) => { result[stack] = Some(String::from_str(v).expect("Ouch")) }
-            None => ()
+fn parse_args(args : &[String]) -> Result<Args, String> {
+    let (file_path, options) = args.split_first().ok_or("Missing file path")?;
+    let mut parsed = Args {
+        file_path: file_path.clone(),
+        cranes: Vec::new(),
+        delay: Duration::from_millis(DEFAULT_DELAY),
+        frames: None,
+        repair: None,
+        start: None,
+        save: None
+    };
+    let mut iter = options.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--crane" => {
+                let name = value()?;
+                parsed.cranes.push(crane(name).ok_or(format!("Unknown crane {}", name))?);
+            },
+            "--delay" => {
+                let delay = value()?;
+                parsed.delay = Duration::from_millis(delay.parse().map_err(|_| format!("Invalid delay {}", delay))?);
+            },
+            "--frames" => parsed.frames = Some(value()?.clone()),
+            "--start" => parsed.start = Some(value()?.clone()),
+            "--save" => parsed.save = Some(value()?.clone()),
+            "--skip" => parsed.repair = Some(Repair::Skip),
+            "--clamp" => parsed.repair = Some(Repair::Clamp),
+            _ => return Err(format!("Unknown option {}", arg))
         }
     }
-    result
+    if parsed.cranes.is_empty() {
+        parsed.cranes.push(crane(DEFAULT_CRANE).expect("Default crane"));
+    }
+    Ok(parsed)
 }
 
-fn process_stack_info(stacks: &mut Vec<VecDeque<String>>, input: &Vec<Option<String>>) -> () {
-    for (index, stack) in input.iter().enumerate() {
-        match stack {
-            None => (),
-            Some(item) => stacks[index].push_back(item.clone())
-        }
+/// Prints the top crates left by each crane.
+fn print_tops(supplies : &Supplies, cranes : &[Box<dyn Crane>]) -> ExitCode {
+    println!("{} stacks, {} moves", supplies.stacks.len(), supplies.moves.len());
+    for crane in cranes {
+        println!("{}: {}", crane.name(), supplies.rearrange_with(crane.as_ref()).join(""));
     }
+    ExitCode::SUCCESS
 }
 
-// move q from f to t
-fn parse_move_order(line: &String) -> (u8, u8, u8) {
-    assert!(line.len() >= 18);
-    let tokens : Vec<&str> = line.split_ascii_whitespace().collect();
-    assert_eq!(6, tokens.len());
-    assert_eq!("move", tokens[0]);
-    let quantity = tokens[1];
-    assert_eq!("from", tokens[2]);
-    let source   = tokens[3];
-    assert_eq!("to", tokens[4]);
-    let target   = tokens[5];
-    (
-        u8::from_str_radix(quantity, 10).expect(""),
-        u8::from_str_radix(source, 10).expect(""),
-        u8::from_str_radix(target, 10).expect(""),
-    )
-} 
+/// Writes the final stacks to the `--save` file, as JSON if its name ends
+/// with `.json`, as a drawing otherwise.
+fn save_state(supplies : &Supplies, cranes : &[Box<dyn Crane>], file_path : &str) -> Result<(), String> {
+    let [crane] = cranes else {
+        return Err(String::from("Only the state of one crane can be saved"));
+    };
+    let stacks = supplies.replay(crane.as_ref(), |_, _| ());
+    let state = if file_path.ends_with(".json") { to_json(&stacks) } else { render(&stacks) };
+    fs::write(file_path, state).map_err(|error| format!("Unable to write file {}: {}", file_path, error))
+}
 
-#[test]
-fn test_move_parsing() -> () {
-    let (quantity, from, to) = parse_move_order(&String::from("move 4 from 3 to 2"));
-    assert_eq!(quantity, 4);
-    assert_eq!(from,     3);
-    assert_eq!(to,       2);
+/// Reads the stacks of a `--start` file, as JSON or as a drawing.
+fn read_start(file_path : &str) -> Result<Vec<VecDeque<String>>, String> {
+    let input = fs::read_to_string(file_path)
+        .map_err(|error| format!("Unable to read file {}: {}", file_path, error))?;
+    let stacks = if file_path.ends_with(".json") { from_json(&input) } else { parse_drawing(&input) };
+    stacks.map_err(|error| format!("Invalid start state at {}", error))
 }
 
-fn move_items(stacks: &mut Vec<VecDeque<String>>, move_order: (u8, u8, u8), mutiple: bool) -> () {
-    let source = move_order.1 as usize;
-    let target = move_order.2 as usize;
-    let quantity = move_order.0 as usize;
-    if mutiple {
-        let mut hold : Vec<String> = Vec::with_capacity(quantity);
-        let src_stack = stacks.get_mut(source - 1)
-            .expect("Source not found");
-        for _ in 0..quantity {
-            hold.push(src_stack.pop_front().expect("No item?"));
-        }
-        let dst_stack = stacks.get_mut(target - 1)
-            .expect("Target not found");
-        for val in hold.iter().rev() {
-            dst_stack.push_front(val.clone());
-        }
-    }
-    else { // One by one
-        for _ in 0..quantity {
-            let src_stack = stacks.get_mut(source - 1)
-                .expect("Source not found");
-            let taken = src_stack.pop_front().expect("No item?");
-            let dst_stack = stacks.get_mut(target - 1)
-                .expect("Target not found");
-            dst_stack.push_front(taken.clone());
+/// Returns the title and the drawing after each move, the initial drawing
+/// first.
+fn frames(supplies : &Supplies, crane : &dyn Crane) -> Vec<(String, String)> {
+    let count = supplies.moves.len();
+    let mut frames = vec![(format!("initial (0/{})", count), render(&supplies.stacks))];
+    supplies.replay(crane, |move_order, stacks| {
+        frames.push((format!("{} ({}/{})", render_move(move_order), frames.len(), count), render(stacks)));
+    });
+    frames
+}
+
+/// Animates the moves in the terminal, or writes the drawing of each frame to
+/// its own file of the `--frames` directory, along with the frame titles, so
+/// that any frame can be used as a `--start` file.
+fn print_frames(supplies : &Supplies, args : &Args) -> ExitCode {
+    let [crane] = &args.cranes[..] else {
+        eprintln!("Only one crane can be shown");
+        return ExitCode::FAILURE;
+    };
+    let frames = frames(supplies, crane.as_ref());
+    match &args.frames {
+        Some(directory) => {
+            let titles : String = frames.iter().map(|(title, _)| format!("{}\n", title)).collect();
+            let written = fs::create_dir_all(directory)
+                .and_then(|_| fs::write(Path::new(directory).join("titles.txt"), titles))
+                .and_then(|_| frames.iter().enumerate().try_for_each(|(index, (_, drawing))| {
+                    fs::write(Path::new(directory).join(format!("frame-{:04}.txt", index)), drawing)
+                }));
+            if let Err(error) = written {
+                eprintln!("Unable to write frames to {}: {}", directory, error);
+                return ExitCode::FAILURE;
+            }
+            println!("{} frames written to {}", frames.len(), directory);
+        },
+        None => for (title, drawing) in &frames {
+            // Clears the terminal before drawing
+            print!("\x1b[2J\x1b[H{}\n{}", title, drawing);
+            thread::sleep(args.delay);
         }
     }
+    ExitCode::SUCCESS
 }
 
-fn process(file_path: &dyn std::convert::AsRef<Path>, stack_count: usize, multiple: bool) -> Vec<String> {
-
-    // File read
-    let f = File::open(file_path).expect("Unable to open file");
-    let mut reader = BufReader::new(f);
-    let mut line_str = String::new();
+/// Prints every invalid move order, and fails if one cannot be applied.
+fn print_check(supplies : &Supplies) -> ExitCode {
+    let (_, invalid) = validate(supplies, Repair::Skip);
+    for invalid_move in &invalid {
+        println!("Invalid move at {}", invalid_move);
+    }
+    println!("{} moves, {} invalid", supplies.moves.len(), invalid.len());
+    if invalid.iter().any(|invalid_move| invalid_move.error.is_fatal()) { ExitCode::FAILURE } else { ExitCode::SUCCESS }
+}
 
-    // Inventory
-    let mut stacks = vec![VecDeque::new(); stack_count];
+fn main() -> ExitCode {
+    // Arguments
+    let args : Vec<String> = env::args().skip(1).collect();
+    let mode = args.first().map(String::as_str).filter(|mode| *mode == "show" || *mode == "check");
+    let options = if mode.is_some() { &args[1..] } else { &args[..] };
+    let args = match parse_args(options) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!("Usage: puzzle-05 [show|check] <file> [--crane {}]... [--skip|--clamp] [--start <file>] [--save <file>] [--delay {}] [--frames <directory>]",
+                CRANE_NAMES.join("|"), DEFAULT_DELAY);
+            return ExitCode::FAILURE;
+        }
+    };
 
     // Reading
-    let mut is_init : bool = true;
-    loop {
-        let len = reader.read_line(&mut line_str)
-            .expect("Unable to read line");
-        //println!("Line={} read={}", line_str, len);
-        if len == 0 {
-            break; // EOF
+    let input = match fs::read_to_string(&args.file_path) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Unable to read file {}: {}", args.file_path, error);
+            return ExitCode::FAILURE;
         }
-        else if line_str.is_empty() || len == 1 /* \n */ {
-            if is_init {
-                println!("Start moves");
-                is_init = false;
-            }
-            else {
-                println!("quit");
-                break;
-            }
+    };
+    let mut supplies = match parse_supplies(&input) {
+        Ok(supplies) => supplies,
+        Err(error) => {
+            eprintln!("Invalid input at {}", error);
+            return ExitCode::FAILURE;
         }
-        else if line_str.starts_with(" 1") {
-            println!("Ignore line");
-            // Instruction to be ignored
-        }
-        else {
-            let input_line = line_str.clone();
-            //rintln!("Before {:?}", stacks);
-            if is_init {
-                let stack_line : Vec<Option<String>> = parse_stack_info(&input_line, stack_count);
-                process_stack_info(&mut stacks, &stack_line);
-            } else {
-                let (q, f, t) = parse_move_order(&input_line);
-                //println!("Parsed move: {}x {}=>{}", q,f,t);
-                move_items(&mut stacks, (q,f,t), multiple);
+    };
+    if let Some(start) = &args.start {
+        match read_start(start) {
+            Ok(stacks) => supplies.stacks = stacks,
+            Err(message) => {
+                eprintln!("{}", message);
+                return ExitCode::FAILURE;
             }
-            //println!("After  {:?}", stacks);
         }
-        line_str.clear();
+    }
+    if mode == Some("check") {
+        return print_check(&supplies);
     }
 
-    //println!("{:?}", stacks);
-    stacks.iter()
-        .map(|deq| deq.iter().next().expect("No item?")) // first element
-        .map(|str| str.clone())
-        .collect()
-}
-
-
-#[test]
-fn test_example1() {
-    let result = process(&"test.log", 3, false);
-    assert_eq!("CMZ", result.join("").as_str());
-}
-
-#[test]
-fn test_example2() {
-    let result = process(&"test.log", 3, true);
-    assert_eq!("MCD", result.join("").as_str());
+    // Validation, keeping the moves of nothing unless repaired
+    let (repaired, invalid) = validate(&supplies, args.repair.unwrap_or(Repair::Skip));
+    for invalid_move in &invalid {
+        eprintln!("Invalid move at {}", invalid_move);
+    }
+    let repaired = match args.repair {
+        Some(_) => repaired,
+        None if invalid.iter().any(|invalid_move| invalid_move.error.is_fatal()) => {
+            eprintln!("Use --skip or --clamp to go on");
+            return ExitCode::FAILURE;
+        },
+        None => supplies
+    };
+
+    if let Some(save) = &args.save {
+        if let Err(message) = save_state(&repaired, &args.cranes, save) {
+            eprintln!("{}", message);
+            return ExitCode::FAILURE;
+        }
+    }
+    if mode == Some("show") {
+        print_frames(&repaired, &args)
+    } else {
+        print_tops(&repaired, &args.cranes)
+    }
 }
-
-fn main() {
-    // 1st argument is the stack count
-    let args_vec : Vec<String> = env::args().collect();
-    let mut args = args_vec.iter();
-    //println!("Arguments: {:?}", args);
-    args.next(); // Program name
-    let file_path = args.next().expect("File path is mandatory");
-    println!("Reading file {}", file_path);
-    let stack_count: usize = args.next()
-        .map(|value| 
-            usize::from_str_radix(value, 10)
-                .expect("Impossible to parse stack count"))
-        .unwrap_or(9);
-    println!("Stack cound {}", stack_count);
-    let multiple_mode : bool = args.next()
-        .map(|arg| arg.parse().expect("Invalid boolean value"))
-        .unwrap_or(true);
-    println!("Multiple mode {}", multiple_mode);
-    
-    // Compute
-    let result = process(file_path, stack_count, multiple_mode);
-
-    // Print
-    println!("{:?}", result.join(""));
-}
\ No newline at end of file
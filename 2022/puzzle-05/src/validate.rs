@@ -0,0 +1,158 @@
+//! Validation of the move orders, as a dry run on the stack heights.
+//!
+//! Every crane moves the ordered quantity from the source stack to the target
+//! stack, so the heights alone tell whether a move order can be applied,
+//! whatever the crane.
+
+use std::fmt;
+
+use crate::drawing::render_move;
+use crate::{MoveOrder, Supplies};
+
+/// Reason why a move order cannot be applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveError {
+    /// The source stack does not exist.
+    UnknownSource,
+    /// The target stack does not exist.
+    UnknownTarget,
+    /// No crate is to be moved.
+    ZeroQuantity,
+    /// The source stack holds fewer crates than ordered.
+    Underflow {
+        /// Crates in the source stack.
+        available: usize
+    },
+    /// The source stack is the target stack.
+    SameStack
+}
+
+impl MoveError {
+    /// Tells whether the move order cannot be applied at all. A zero
+    /// quantity or a move within a stack can be applied, moving nothing:
+    /// moves within a stack are not replayed by any crane.
+    pub fn is_fatal(&self) -> bool {
+        matches!(self, MoveError::UnknownSource | MoveError::UnknownTarget | MoveError::Underflow { .. })
+    }
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveError::UnknownSource => write!(f, "Unknown source stack"),
+            MoveError::UnknownTarget => write!(f, "Unknown target stack"),
+            MoveError::ZeroQuantity => write!(f, "Zero quantity"),
+            MoveError::Underflow { available } => write!(f, "Only {} crates in the source stack", available),
+            MoveError::SameStack => write!(f, "Same source and target stack")
+        }
+    }
+}
+
+/// A move order that cannot be applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidMove {
+    /// Index of the move order, from 0.
+    pub index: usize,
+    /// Line of the move order in the input, from 1.
+    pub line: usize,
+    /// The move order.
+    pub move_order: MoveOrder,
+    /// Why it cannot be applied.
+    pub error: MoveError
+}
+
+impl fmt::Display for InvalidMove {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {} `{}`", self.line, self.error, render_move(self.move_order))
+    }
+}
+
+/// What to do with the move orders that cannot be applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repair {
+    /// The move order is dropped.
+    Skip,
+    /// The quantity is lowered to the crates of the source stack. Move
+    /// orders that still cannot be applied are dropped.
+    Clamp
+}
+
+/// Checks a move order against the stack heights.
+pub fn check(heights: &[usize], move_order: MoveOrder) -> Result<(), MoveError> {
+    let (quantity, source, target) = move_order;
+    let height = |stack: u8| (stack as usize).checked_sub(1).and_then(|index| heights.get(index));
+    let available = *height(source).ok_or(MoveError::UnknownSource)?;
+    height(target).ok_or(MoveError::UnknownTarget)?;
+    if quantity == 0 {
+        Err(MoveError::ZeroQuantity)
+    } else if available < quantity as usize {
+        Err(MoveError::Underflow { available })
+    } else if source == target {
+        Err(MoveError::SameStack)
+    } else {
+        Ok(())
+    }
+}
+
+/// Replays the move orders of `supplies` on the stack heights, and reports
+/// every move order that cannot be applied. Returns the supplies holding the
+/// move orders kept by `repair`, which any crane can apply.
+pub fn validate(supplies: &Supplies, repair: Repair) -> (Supplies, Vec<InvalidMove>) {
+    let mut heights : Vec<usize> = supplies.stacks.iter().map(|stack| stack.len()).collect();
+    let mut repaired = Supplies { stacks: supplies.stacks.clone(), moves: Vec::new(), move_lines: Vec::new() };
+    let mut invalid = Vec::new();
+    for (index, (move_order, line)) in supplies.moves.iter().zip(&supplies.move_lines).enumerate() {
+        let kept = match check(&heights, *move_order) {
+            Ok(()) => Some(*move_order),
+            Err(error) => {
+                invalid.push(InvalidMove { index, line: *line, move_order: *move_order, error });
+                match (repair, error) {
+                    (Repair::Clamp, MoveError::Underflow { available }) if available > 0 =>
+                        Some((available as u8, move_order.1, move_order.2)),
+                    _ => None
+                }
+            }
+        };
+        if let Some(move_order @ (quantity, source, target)) = kept {
+            heights[source as usize - 1] -= quantity as usize;
+            heights[target as usize - 1] += quantity as usize;
+            repaired.moves.push(move_order);
+            repaired.move_lines.push(*line);
+        }
+    }
+    (repaired, invalid)
+}
+
+#[test]
+fn test_check() {
+    let heights = [2, 0, 1];
+    assert_eq!(Ok(()), check(&heights, (2, 1, 2)));
+    assert_eq!(Err(MoveError::UnknownSource), check(&heights, (1, 0, 2)));
+    assert_eq!(Err(MoveError::UnknownTarget), check(&heights, (1, 1, 4)));
+    assert_eq!(Err(MoveError::ZeroQuantity), check(&heights, (0, 1, 2)));
+    assert_eq!(Err(MoveError::SameStack), check(&heights, (1, 3, 3)));
+    assert_eq!(Err(MoveError::Underflow { available: 0 }), check(&heights, (1, 2, 1)));
+    assert_eq!(Err(MoveError::Underflow { available: 1 }), check(&heights, (2, 3, 3)));
+    assert!(!MoveError::SameStack.is_fatal() && MoveError::UnknownTarget.is_fatal());
+}
+
+#[test]
+fn test_validate() {
+    let input = "[A]\n[B] [C]\n 1   2   3\n\nmove 3 from 1 to 3\nmove 0 from 2 to 1\nmove 1 from 2 to 2\nmove 2 from 3 to 4\nmove 1 from 2 to 3\n";
+    let supplies = crate::parse_supplies(input).expect("Valid syntax");
+    let (skipped, invalid) = validate(&supplies, Repair::Skip);
+    let errors : Vec<(usize, MoveError)> = invalid.iter().map(|invalid| (invalid.line, invalid.error)).collect();
+    assert_eq!(vec![
+        (5, MoveError::Underflow { available: 2 }),
+        (6, MoveError::ZeroQuantity),
+        (7, MoveError::SameStack),
+        (8, MoveError::UnknownTarget)],
+        errors);
+    assert_eq!((vec![(1, 2, 3)], vec![9]), (skipped.moves, skipped.move_lines));
+    assert_eq!("line 5: Only 2 crates in the source stack `move 3 from 1 to 3`", invalid[0].to_string());
+
+    let (clamped, invalid) = validate(&supplies, Repair::Clamp);
+    assert_eq!(4, invalid.len());
+    assert_eq!(vec![(2, 1, 3), (1, 2, 3)], clamped.moves);
+    assert_eq!(vec!["", "", "C"], clamped.rearrange(true));
+}
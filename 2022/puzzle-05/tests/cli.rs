@@ -0,0 +1,55 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Runs the program from the puzzle folder and returns its standard output.
+fn run(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_puzzle-05"))
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .expect("Program run");
+    assert!(output.status.success(), "{:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).expect("UTF-8 output")
+}
+
+/// Returns an empty working directory, specific to the test.
+fn work_dir(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("puzzle-05-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).expect("Working directory");
+    directory
+}
+
+fn path(directory: &Path, name: &str) -> String {
+    directory.join(name).to_str().expect("UTF-8 path").to_string()
+}
+
+#[test]
+fn test_save_and_start() {
+    let directory = work_dir("state");
+    // Three empty stacks and no move: the start state is saved unchanged
+    let no_moves = path(&directory, "no-moves.log");
+    fs::write(&no_moves, " 1   2   3\n").expect("Input file");
+    for name in ["state.txt", "state.json"] {
+        let saved = path(&directory, name);
+        let resaved = path(&directory, &format!("again-{}", name));
+        assert!(run(&["test.log", "--crane", "9000", "--save", &saved]).contains("9000: CMZ"));
+        assert!(run(&[&no_moves, "--start", &saved, "--save", &resaved]).contains("9001: CMZ"));
+        assert_eq!(fs::read_to_string(&saved).expect("Saved"), fs::read_to_string(&resaved).expect("Saved again"));
+    }
+    fs::remove_dir_all(&directory).expect("Cleanup");
+}
+
+#[test]
+fn test_frames_as_start() {
+    let directory = work_dir("frames");
+    let frames = path(&directory, "frames");
+    run(&["show", "test.log", "--crane", "9000", "--frames", &frames]);
+    assert_eq!(5, fs::read_to_string(path(&directory, "frames/titles.txt")).expect("Titles").lines().count());
+    let last = path(&directory, "frames/frame-0004.txt");
+    let no_moves = path(&directory, "no-moves.log");
+    fs::write(&no_moves, " 1   2   3\n").expect("Input file");
+    assert!(run(&[&no_moves, "--start", &last]).contains("9001: CMZ"));
+    fs::remove_dir_all(&directory).expect("Cleanup");
+}
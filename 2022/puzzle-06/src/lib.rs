@@ -0,0 +1,146 @@
+//! Day 6: Tuning Trouble
+//!
+//! Finds the markers of a datastream buffer.
+
+#![warn(missing_docs)]
+
+use aoc_common::{ParseError, Solution};
+
+/// Returns `true` only if all characters are different.
+pub fn different_chars(input: &str) -> bool {
+    let chars = input.as_bytes();
+    for i in 0..(chars.len()-1) {
+        for j in (i+1)..chars.len() {
+            if chars[i] == chars[j] {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/**
+  Returns the first index followed by n different characters.
+  
+  Or `0` if not found. Panics if `size` is lower than 2.
+*/
+pub fn get_start_index(input: &str, size: usize) -> usize {
+    assert!(size > 1);
+    if input.len() < size {
+        return 0; // Too short
+    }
+    let mut first_chars : &str = &input[0..size];
+    let max_index = input.len()-1;
+    for i in size..max_index {
+        if different_chars(first_chars) {
+            return i;
+        }
+        else {
+            let end_index = i + 1;
+            let start_index = end_index - size;
+            first_chars = &input[start_index..end_index];
+        }
+    }
+    0 // Not found
+}
+
+#[test]
+fn test_1() {
+    let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+    assert_eq!(7, get_start_index(input, 4));
+}
+
+#[test]
+fn test_2() {
+    let input = "bvwbjplbgvbhsrlpgdmjqwftvncz";
+    assert_eq!(5, get_start_index(input, 4));
+}
+
+#[test]
+fn test_3() {
+    let input = "nppdvjthqldpwncqszvftbrmjlhg";
+    assert_eq!(6, get_start_index(input, 4));
+}
+
+#[test]
+fn test_4() {
+    let input = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
+    assert_eq!(10, get_start_index(input, 4));
+}
+
+#[test]
+fn test_5() {
+    let input = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
+    assert_eq!(11, get_start_index(input, 4));
+}
+
+#[test]
+fn test_6() {
+    let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+    assert_eq!(19, get_start_index(input, 14));
+}
+
+#[test]
+fn test_7() {
+    let input = "bvwbjplbgvbhsrlpgdmjqwftvncz";
+    assert_eq!(23, get_start_index(input, 14));
+}
+
+#[test]
+fn test_8() {
+    let input = "nppdvjthqldpwncqszvftbrmjlhg";
+    assert_eq!(23, get_start_index(input, 14));
+}
+
+#[test]
+fn test_9() {
+    let input = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
+    assert_eq!(29, get_start_index(input, 14));
+}
+
+#[test]
+fn test_10() {
+    let input = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
+    assert_eq!(26, get_start_index(input, 14));
+}
+
+/// Count of different characters of a start-of-message marker.
+pub const MESSAGE_MARKER_SIZE : usize = 14;
+
+/// The datastream buffer received by the device.
+#[derive(Debug)]
+pub struct Datastream {
+    /// Characters received.
+    pub buffer: String
+}
+
+impl Solution for Datastream {
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        for (index, line) in input.lines().enumerate() {
+            if let Some((column, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+                return Err(ParseError::new(index + 1, column + 1, &c.to_string(), "Invalid character"));
+            }
+        }
+        Ok(Datastream { buffer: input.to_string() })
+    }
+
+    /// Index of the start-of-packet marker, `0` if not found.
+    fn part1(&self) -> usize {
+        get_start_index(&self.buffer, 4)
+    }
+
+    /// Index of the start-of-message marker, `0` if not found.
+    fn part2(&self) -> usize {
+        get_start_index(&self.buffer, MESSAGE_MARKER_SIZE)
+    }
+}
+
+#[test]
+fn test_invalid_character() {
+    let error = Datastream::parse("mjqjpqm-bljsp\n").expect_err("Invalid character");
+    assert_eq!((1, 8), (error.line, error.column));
+    assert_eq!(0, get_start_index("abc", 4));
+}
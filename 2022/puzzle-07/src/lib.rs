@@ -0,0 +1,332 @@
+//! Day 7: No Space Left On Device
+//!
+//! Computes the size of folders from a terminal output.
+
+#![warn(missing_docs)]
+
+use std::{fs, collections::HashMap};
+
+use aoc_common::{ParseError, Solution};
+
+/// Reads the size of a listed file, written as `size name`.
+pub fn read_size(line_str: &str) -> Result<u32, &'static str> {
+    let (first, _) = line_str.split_once(' ')
+        .ok_or("Malformed listing entry")?;
+    first.parse::<u32>()
+        .map_err(|_| "Invalid file size")
+}
+
+#[test]
+fn test_read_size() {
+    assert_eq!(Ok(14848514), read_size("14848514 b.txt"));
+    assert!(read_size("b.txt").is_err());
+}
+
+/// A command of the device terminal.
+#[allow(clippy::enum_variant_names)]
+pub enum ElveDeviceCommand {
+    /// `cd name`
+    ToSubDir(String),
+    /// `cd ..`
+    ToParentDir,
+    /// `cd /`
+    ToRootDir,
+    /// `ls`
+    ListDir
+}
+
+/// Parses a command line, without its `$ ` prompt.
+pub fn parse_command(cmd: &str) -> Result<ElveDeviceCommand, &'static str> {
+    if let Some(path) = cmd.strip_prefix("cd ") {
+        if path == DELIMITER {
+            Ok(ElveDeviceCommand::ToRootDir)
+        }
+        else if path == ".." {
+            Ok(ElveDeviceCommand::ToParentDir)
+        }
+        else {
+            Ok(ElveDeviceCommand::ToSubDir(path.to_string()))
+        }
+    }
+    else if cmd == "ls" {
+        Ok(ElveDeviceCommand::ListDir)
+    }
+    else {
+        Err("Unsupported command")
+    }
+}
+
+/// Root folder, also used as path delimiter.
+pub static DELIMITER : &str = "/";
+
+/// Returns the new current folder after the command, or `None` if the command does not move.
+pub fn navigate(pwd: String, command: ElveDeviceCommand) -> Option<String> {
+    match command {
+        ElveDeviceCommand::ToRootDir => {
+            Some(DELIMITER.to_string())
+        },
+        ElveDeviceCommand::ToParentDir => {
+            Some(to_parent_dir(&pwd))
+        },
+        ElveDeviceCommand::ToSubDir(dst) => {
+            Some(to_sub_dir(&pwd, &dst))
+        },
+        ElveDeviceCommand::ListDir => None
+    }
+}
+
+/// Returns the parent folder path.
+pub fn to_parent_dir(pwd: &str) -> String {
+    let folders = pwd.split(DELIMITER).collect::<Vec<&str>>();
+    let new_folder_count = folders.len() - 1;
+    if new_folder_count > 1 {
+        folders[0..new_folder_count].join(DELIMITER)
+    }
+    else {
+        DELIMITER.to_string()
+    }
+}
+
+#[test]
+fn test_parentdir() {
+    assert_eq!("/", to_parent_dir("/foo"));
+    assert_eq!("/foo", to_parent_dir("/foo/bar"));
+}
+
+/// Returns the path of the `dst` sub folder.
+pub fn to_sub_dir(pwd: &str, dst: &str) -> String {
+    let mut new_pwd = pwd.to_string();
+    if pwd != DELIMITER {
+        new_pwd.push_str(DELIMITER);
+    }
+    new_pwd.push_str(dst);
+    new_pwd
+}
+
+#[test]
+fn test_subdir() {
+    assert_eq!("/foo", to_sub_dir("/", "foo"));
+    assert_eq!("/foo/bar", to_sub_dir("/foo", "bar"));
+}
+
+/// Returns the path of the folder and of all its ancestors.
+pub fn ascending(folder: &str) -> Vec<String> {    
+    let tokens : Vec<&str> = folder.split(DELIMITER).collect();
+    if tokens.len() < 2 {
+        panic!("what?")
+    }
+    let mut folders : Vec<String> = Vec::with_capacity(tokens.len());
+    folders.push(DELIMITER.to_string());
+    let mut advance : String = String::new(); 
+    for token in tokens.iter().skip(1) {
+        if !token.is_empty() {
+            advance.push_str(DELIMITER);
+            advance.push_str(token);
+            folders.push(advance.clone())
+        }
+    }
+    folders
+}
+
+#[test]
+fn test_ascending_3() {
+    let folders = ascending("/foo/bar/baz");
+    assert!(folders.contains(&"/".to_string()));
+    assert!(folders.contains(&"/foo".to_string()));
+    assert!(folders.contains(&"/foo/bar".to_string()));
+    assert!(folders.contains(&"/foo/bar/baz".to_string()));
+    assert_eq!(4, folders.len());
+}
+
+#[test]
+fn test_ascending_0() {
+    let folders = ascending("/");
+    assert!(folders.contains(&"/".to_string()));
+    assert_eq!(1, folders.len());
+}
+
+#[test]
+fn test_ascending_1() {
+    let folders = ascending("/foo");
+    assert!(folders.contains(&"/".to_string()));
+    assert!(folders.contains(&"/foo".to_string()));
+    assert_eq!(2, folders.len());
+}
+
+/// Adds `size` to the folder and all its ancestors. Fails, leaving the sizes
+/// unchanged, when a folder size would overflow.
+pub fn flush_sizes(by_folder: &mut HashMap<String, u32>, size: u32, folder: &str) -> Result<(), &'static str> {
+    let mut new_sizes = Vec::new();
+    for f in ascending(folder) {
+        let val = by_folder.get(&f);
+        let new_size : u32 = match val {
+            None => size,
+            Some(old_size) => size.checked_add(*old_size).ok_or("Folder size overflow")?
+        };
+        new_sizes.push((f, new_size));
+    }
+    //println!("Insert {:?}", new_sizes);
+    by_folder.extend(new_sizes);
+    Ok(())
+}
+
+/// Reads the terminal output of a file and returns the total size of each folder.
+pub fn read_file(file_name: &str) -> Result<HashMap<String, u32>, String> {
+    let input = fs::read_to_string(file_name)
+        .map_err(|error| format!("Unable to read {}: {}", file_name, error))?;
+    read_listing(&input).map_err(|error| format!("Invalid input {} at {}", file_name, error))
+}
+
+/// Reads a terminal output and returns the total size of each folder.
+pub fn read_listing(input: &str) -> Result<HashMap<String, u32>, ParseError> {
+
+    // Folders and corresponding size
+    let mut folders : HashMap<String, u32> = HashMap::new();
+    // Current folder
+    let mut pwd     : String = DELIMITER.to_string();
+    // Output mode
+    let mut listing : bool = false;
+    // Total size
+    let mut total_size: u32 = 0;
+
+    for (index, line_str) in input.lines().enumerate() {
+        let line_number = index + 1;
+        // Processing command
+        if let Some(command_str) = line_str.strip_prefix("$ ") {
+            if listing {
+                flush_sizes(&mut folders, total_size, &pwd)
+                    .map_err(|message| ParseError::new(line_number, 1, line_str, message))?;
+                total_size = 0;
+                listing = false;
+            }
+            let cmd = parse_command(command_str)
+                .map_err(|message| ParseError::in_line(line_number, line_str, command_str, message))?;
+            match cmd {
+                ElveDeviceCommand::ListDir => {
+                    listing = true
+                },
+                _ => {
+                    let new_path = navigate(pwd, cmd).expect("No new path");
+                    //println!("New path {}", new_path);
+                    pwd = new_path;
+                }
+            };
+        }
+        // Reading output
+        else if listing {
+            if ! line_str.starts_with("dir") {
+                total_size = read_size(line_str)
+                    .and_then(|size| total_size.checked_add(size).ok_or("Folder size overflow"))
+                    .map_err(|message| ParseError::new(line_number, 1, line_str, message))?;
+            }
+        }
+        else {
+            return Err(ParseError::new(line_number, 1, line_str, "Output outside of a listing"));
+        }
+    }
+    if listing {
+        let line_str = input.lines().last().unwrap_or("");
+        flush_sizes(&mut folders, total_size, &pwd)
+            .map_err(|message| ParseError::new(input.lines().count(), 1, line_str, message))?;
+    }
+
+    Ok(folders)
+}
+
+#[test]
+fn test_1() {
+    let rs = read_file("test.log").expect("Valid input");
+    assert_eq!(95437, sum_of_less_than(&rs, 100000));
+    assert!(read_file("missing.log").expect_err("No file").starts_with("Unable to read missing.log"));
+}
+
+/// Returns the sum of sizes of folders whose size is at most `folder_max_size`.
+pub fn sum_of_less_than(folders: &HashMap<String, u32>, folder_max_size: u32) -> u32 {
+    folders.values().copied()
+        .filter(|size| size <= &folder_max_size)
+        .sum()
+}
+
+#[test]
+fn test_2() {
+    let rs = read_file("test.log").expect("Valid input");
+    assert_eq!(Some(24933642), smallest_greater_than(&rs, 8381165));
+}
+
+/// Returns the size of the smallest folder whose size is at least
+/// `folder_min_size`, if any.
+pub fn smallest_greater_than(folders: &HashMap<String, u32>, folder_min_size: u32) -> Option<u32> {
+    folders.values().copied()
+        .filter(|size| size >= &folder_min_size)
+        .min()
+}
+
+/// Maximum used space for an update
+/// (See part2 description)
+pub static MAX_STORAGE_SIZE : u32 = 70_000_000 - 30_000_000;
+
+/// Total size of each folder of the device, by absolute path.
+#[derive(Debug)]
+pub struct FileSystem {
+    /// Total size by folder path.
+    pub folders: HashMap<String, u32>
+}
+
+impl FileSystem {
+
+    /// Returns the total size of the root folder, 0 when nothing is listed.
+    pub fn used_space(&self) -> u32 {
+        self.folders.get(DELIMITER).copied().unwrap_or(0)
+    }
+
+    /// Returns the space to be reclaimed before an update, 0 when there is
+    /// already enough free space.
+    pub fn required_space(&self) -> u32 {
+        self.used_space().saturating_sub(MAX_STORAGE_SIZE)
+    }
+}
+
+impl Solution for FileSystem {
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let folders = read_listing(input)?;
+        if folders.is_empty() {
+            return Err(ParseError::new(input.lines().count().max(1), 1, "", "No folder listed"));
+        }
+        Ok(FileSystem { folders })
+    }
+
+    fn part1(&self) -> u32 {
+        sum_of_less_than(&self.folders, 100000)
+    }
+
+    fn part2(&self) -> u32 {
+        // The root folder holds at least the required space
+        smallest_greater_than(&self.folders, self.required_space()).expect("Root folder")
+    }
+}
+
+#[test]
+fn test_unsupported_command() {
+    let error = read_listing("$ cd /\n$ rm -rf a\n").expect_err("Unsupported command");
+    assert_eq!((2, 3), (error.line, error.column));
+    assert_eq!("rm -rf a", error.text);
+}
+
+#[test]
+fn test_small_listings() {
+    let error = FileSystem::parse("$ cd /\n").expect_err("No folder");
+    assert_eq!((1, "No folder listed"), (error.line, error.message.as_str()));
+    let file_system = FileSystem::parse("$ cd /\n$ ls\n100 a\n").expect("Valid input");
+    assert_eq!((0, 100, 100), (file_system.required_space(), file_system.part1(), file_system.part2()));
+}
+
+#[test]
+fn test_size_overflow() {
+    let error = read_listing("$ cd /\n$ ls\n4000000000 a\n300000000 b\n").expect_err("File overflow");
+    assert_eq!((4, "Folder size overflow"), (error.line, error.message.as_str()));
+    let error = read_listing("$ cd /\n$ ls\n4000000000 a\ndir b\n$ cd b\n$ ls\n300000000 c\n").expect_err("Folder overflow");
+    assert_eq!((7, "Folder size overflow"), (error.line, error.message.as_str()));
+}
@@ -0,0 +1,24 @@
+use std::fs;
+use std::hint::black_box;
+
+use aoc::puzzles::{workspace_root, PUZZLES};
+use aoc_common::Part;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Times the parsing and each part of every puzzle, on its `input.log` file.
+fn bench_puzzles(c: &mut Criterion) {
+    for puzzle in PUZZLES {
+        let path = workspace_root().join(puzzle.folder()).join("input.log");
+        let input = fs::read_to_string(&path).expect("Unable to read input");
+        let solution = (puzzle.parse)(&input).expect("Invalid input");
+
+        let mut group = c.benchmark_group(format!("{}-{:02}", puzzle.year, puzzle.day));
+        group.bench_function("parse", |b| b.iter(|| (puzzle.parse)(black_box(&input))));
+        group.bench_function("part1", |b| b.iter(|| solution.answer(black_box(Part::One))));
+        group.bench_function("part2", |b| b.iter(|| solution.answer(black_box(Part::Two))));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_puzzles);
+criterion_main!(benches);
@@ -0,0 +1,123 @@
+//! Expected answers of the puzzles.
+//!
+//! Each puzzle folder holds an `answers.toml` file, with one table per input
+//! file of the folder. A table is named after the input file without its
+//! `.log` extension and holds the expected answer of each part:
+//!
+//! ```toml
+//! [test]
+//! part1 = "CMZ"
+//! part2 = "MCD"
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_common::Part;
+use toml::{Table, Value};
+
+use crate::puzzles::Puzzle;
+
+/// Name of the answers file of a puzzle folder.
+pub static ANSWERS_FILE: &str = "answers.toml";
+
+/// An expected answer for an input file of a puzzle folder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    /// Input file name, relative to the puzzle folder.
+    pub input: String,
+    /// Solved part.
+    pub part: Part,
+    /// Expected answer.
+    pub answer: String,
+}
+
+/// Finds the puzzle folders below `root`, as (year, day, folder).
+///
+/// Puzzle folders are named `<year>/puzzle-<day>`.
+pub fn discover(root: &Path) -> Result<Vec<(u16, u8, PathBuf)>, String> {
+    let read_dir = |path: &Path| fs::read_dir(path)
+        .map_err(|e| format!("Unable to list {}: {}", path.display(), e));
+    let mut folders: Vec<(u16, u8, PathBuf)> = Vec::new();
+    for year_entry in read_dir(root)?.flatten() {
+        let year_path = year_entry.path();
+        let year: Option<u16> = year_entry.file_name().to_str().and_then(|name| name.parse().ok());
+        let Some(year) = year.filter(|_| year_path.is_dir()) else { continue };
+        for day_entry in read_dir(&year_path)?.flatten() {
+            let day: Option<u8> = day_entry.file_name().to_str()
+                .and_then(|name| name.strip_prefix("puzzle-"))
+                .and_then(|day| day.parse().ok());
+            if let Some(day) = day {
+                folders.push((year, day, day_entry.path()));
+            }
+        }
+    }
+    folders.sort();
+    Ok(folders)
+}
+
+/// Parses the content of an answers file.
+pub fn parse_answers(content: &str) -> Result<Vec<Expected>, String> {
+    let table: Table = content.parse().map_err(|e| format!("Invalid TOML: {}", e))?;
+    let mut expected: Vec<Expected> = Vec::new();
+    for (name, value) in table {
+        let answers = value.as_table().ok_or(format!("[{}] is not a table", name))?;
+        for (key, answer) in answers {
+            let part = match key.as_str() {
+                "part1" => Part::One,
+                "part2" => Part::Two,
+                _ => return Err(format!("Unknown key {} in [{}]", key, name)),
+            };
+            let answer = match answer {
+                Value::String(s) => s.clone(),
+                Value::Integer(i) => i.to_string(),
+                _ => return Err(format!("Invalid answer for {} in [{}]", key, name)),
+            };
+            expected.push(Expected { input: format!("{}.log", name), part, answer });
+        }
+    }
+    Ok(expected)
+}
+
+/// Reads the expected answers of a puzzle folder.
+pub fn read_answers(folder: &Path) -> Result<Vec<Expected>, String> {
+    let path = folder.join(ANSWERS_FILE);
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+    parse_answers(&content).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Solves every input of a puzzle folder and returns a message for each
+/// answer that differs from the expected one.
+pub fn check(puzzle: &Puzzle, folder: &Path) -> Vec<String> {
+    let expected = match read_answers(folder) {
+        Ok(expected) => expected,
+        Err(message) => return vec![message],
+    };
+    let mut failures: Vec<String> = Vec::new();
+    for e in expected {
+        let path = folder.join(&e.input);
+        let answer = fs::read_to_string(&path)
+            .map_err(|err| format!("Unable to read {}: {}", path.display(), err))
+            .and_then(|input| (puzzle.parse)(&input)
+                .map_err(|err| format!("Invalid input {} at {}", path.display(), err)))
+            .map(|solution| solution.answer(e.part));
+        match answer {
+            Ok(answer) if answer == e.answer => (),
+            Ok(answer) => failures.push(format!("{} part {}: expected {} but got {}",
+                path.display(), e.part, e.answer, answer)),
+            Err(message) => failures.push(message),
+        }
+    }
+    failures
+}
+
+#[test]
+fn test_parse_answers() {
+    let expected = parse_answers("[test]\npart1 = \"CMZ\"\npart2 = 12\n").expect("Valid answers");
+    assert_eq!(vec![
+        Expected { input: "test.log".to_string(), part: Part::One, answer: "CMZ".to_string() },
+        Expected { input: "test.log".to_string(), part: Part::Two, answer: "12".to_string() },
+    ], expected);
+    assert!(parse_answers("[test]\npart3 = 1\n").is_err());
+}
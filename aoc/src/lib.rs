@@ -0,0 +1,7 @@
+//! Runs the puzzles of all years by their year and day.
+
+#![warn(missing_docs)]
+
+pub mod answers;
+pub mod puzzles;
+pub mod timing;
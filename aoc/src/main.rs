@@ -0,0 +1,125 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::{env, fs};
+
+use aoc::puzzles::{self, workspace_root, Puzzle};
+use aoc::timing::{self, solve_timed, Timing};
+use aoc_common::Part;
+
+static USAGE: &str = "Usage: aoc run <year> [<day> [input file]] [--part <1|2>] [--time]";
+
+/// Arguments of the `run` command.
+struct RunArgs {
+    year: u16,
+    /// All days of the year when missing
+    day: Option<u8>,
+    part: Option<Part>,
+    file: Option<String>,
+    time: bool,
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    let mut positional: Vec<&String> = Vec::new();
+    let mut part: Option<Part> = None;
+    let mut time: bool = false;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--part" || arg == "-p" {
+            let value = iter.next().ok_or("Missing value for --part")?;
+            part = Some(value.parse()?);
+        }
+        else if arg == "--time" || arg == "-t" {
+            time = true;
+        }
+        else {
+            positional.push(arg);
+        }
+    }
+    if positional.is_empty() || positional.len() > 3 {
+        return Err(USAGE.to_string());
+    }
+    Ok(RunArgs {
+        year: positional[0].parse().map_err(|_| format!("Invalid year {}", positional[0]))?,
+        day: positional.get(1)
+            .map(|day| day.parse().map_err(|_| format!("Invalid day {}", day)))
+            .transpose()?,
+        part,
+        file: positional.get(2).map(|s| s.to_string()),
+        time,
+    })
+}
+
+fn run(args: RunArgs) -> Result<(), String> {
+    let selected: Vec<&Puzzle> = match args.day {
+        Some(day) => vec![puzzles::find(args.year, day)
+            .ok_or(format!("No puzzle for year {} day {}", args.year, day))?],
+        None => puzzles::PUZZLES.iter().filter(|p| p.year == args.year).collect(),
+    };
+    if selected.is_empty() {
+        return Err(format!("No puzzle for year {}", args.year));
+    }
+    let parts: Vec<Part> = match args.part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+    let mut timings: Vec<Timing> = Vec::with_capacity(selected.len());
+    for puzzle in selected {
+        // Defaults to the input of the puzzle folder
+        let file: PathBuf = args.file.as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| workspace_root().join(puzzle.folder()).join("input.log"));
+        let input = fs::read_to_string(&file)
+            .map_err(|e| format!("Unable to read {}: {}", file.display(), e))?;
+        let (answers, timing) = solve_timed(puzzle, &input, &parts)
+            .map_err(|e| format!("Invalid input {} at {}", file.display(), e))?;
+        for (part, answer) in answers {
+            println!("{} day {} part {}: {}", puzzle.year, puzzle.day, part, answer);
+        }
+        timings.push(timing);
+    }
+    if args.time {
+        println!();
+        println!("{}", timing::table(&timings));
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("run") => parse_run_args(&args[1..]).and_then(run),
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[test]
+fn test_parse_run_args() {
+    let args: Vec<String> = ["2022", "5", "--part", "2", "input.log"].iter().map(|s| s.to_string()).collect();
+    let run_args = parse_run_args(&args).expect("Valid arguments");
+    assert_eq!(2022, run_args.year);
+    assert_eq!(Some(5), run_args.day);
+    assert_eq!(Some(Part::Two), run_args.part);
+    assert_eq!(Some("input.log".to_string()), run_args.file);
+    assert!(!run_args.time);
+}
+
+#[test]
+fn test_parse_run_args_whole_year() {
+    let args: Vec<String> = ["2022", "--time"].iter().map(|s| s.to_string()).collect();
+    let run_args = parse_run_args(&args).expect("Valid arguments");
+    assert_eq!(None, run_args.day);
+    assert!(run_args.time);
+}
+
+#[test]
+fn test_parse_run_args_invalid_part() {
+    let args: Vec<String> = ["2022", "5", "--part", "3"].iter().map(|s| s.to_string()).collect();
+    assert!(parse_run_args(&args).is_err());
+}
@@ -0,0 +1,47 @@
+//! Registry of the solved puzzles.
+
+use std::path::{Path, PathBuf};
+
+use aoc_common::{parse_any, AnySolution, ParseError};
+
+/// Parses the whole input content of a puzzle.
+pub type ParseFn = fn(&str) -> Result<Box<dyn AnySolution>, ParseError>;
+
+/// A registered puzzle, identified by its year and day.
+pub struct Puzzle {
+    /// Year of the event.
+    pub year: u16,
+    /// Day of the puzzle, from 1 to 25.
+    pub day: u8,
+    /// Parser of the puzzle input.
+    pub parse: ParseFn,
+}
+
+impl Puzzle {
+
+    /// Returns the folder of the puzzle, relative to the workspace root.
+    pub fn folder(&self) -> PathBuf {
+        PathBuf::from(self.year.to_string()).join(format!("puzzle-{:02}", self.day))
+    }
+}
+
+/// All solved puzzles.
+pub static PUZZLES: &[Puzzle] = &[
+    Puzzle { year: 2022, day: 1, parse: parse_any::<puzzle_01::Calories> },
+    Puzzle { year: 2022, day: 2, parse: parse_any::<puzzle_02::StrategyGuide> },
+    Puzzle { year: 2022, day: 3, parse: parse_any::<puzzle_03::Rucksacks> },
+    Puzzle { year: 2022, day: 4, parse: parse_any::<puzzle_04::Assignments> },
+    Puzzle { year: 2022, day: 5, parse: parse_any::<puzzle_05::Supplies> },
+    Puzzle { year: 2022, day: 6, parse: parse_any::<puzzle_06::Datastream> },
+    Puzzle { year: 2022, day: 7, parse: parse_any::<puzzle_07::FileSystem> },
+];
+
+/// Returns the puzzle of the given year and day, if solved.
+pub fn find(year: u16, day: u8) -> Option<&'static Puzzle> {
+    PUZZLES.iter().find(|p| p.year == year && p.day == day)
+}
+
+/// Root folder of the workspace, where puzzle folders are located.
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().expect("No workspace root")
+}
@@ -0,0 +1,83 @@
+//! Timing of puzzle solving.
+
+use std::time::{Duration, Instant};
+
+use aoc_common::{AnySolution, ParseError, Part};
+
+use crate::puzzles::Puzzle;
+
+/// Durations of the parsing and of each solved part of a puzzle.
+#[derive(Debug, Clone)]
+pub struct Timing {
+    /// Year of the puzzle.
+    pub year: u16,
+    /// Day of the puzzle.
+    pub day: u8,
+    /// Duration of the input parsing.
+    pub parse: Duration,
+    /// Duration of each solved part.
+    pub parts: Vec<(Part, Duration)>,
+}
+
+impl Timing {
+
+    /// Returns the duration of the given part, if solved.
+    pub fn part(&self, part: Part) -> Option<Duration> {
+        self.parts.iter().find(|(p, _)| *p == part).map(|(_, d)| *d)
+    }
+
+    /// Returns the whole duration, parsing included.
+    pub fn total(&self) -> Duration {
+        self.parse + self.parts.iter().map(|(_, d)| *d).sum::<Duration>()
+    }
+}
+
+/// Parses the input and solves the given parts, while measuring durations.
+pub fn solve_timed(puzzle: &Puzzle, input: &str, parts: &[Part]) -> Result<(Vec<(Part, String)>, Timing), ParseError> {
+    let start = Instant::now();
+    let solution: Box<dyn AnySolution> = (puzzle.parse)(input)?;
+    let parse = start.elapsed();
+    let mut answers: Vec<(Part, String)> = Vec::with_capacity(parts.len());
+    let mut durations: Vec<(Part, Duration)> = Vec::with_capacity(parts.len());
+    for part in parts {
+        let start = Instant::now();
+        let answer = solution.answer(*part);
+        durations.push((*part, start.elapsed()));
+        answers.push((*part, answer));
+    }
+    Ok((answers, Timing { year: puzzle.year, day: puzzle.day, parse, parts: durations }))
+}
+
+fn format_duration(duration: Option<Duration>) -> String {
+    duration.map_or("-".to_string(), |d| format!("{:.2?}", d))
+}
+
+/// Formats timings as a table, one row per day.
+pub fn table(timings: &[Timing]) -> String {
+    let mut lines: Vec<String> = Vec::with_capacity(timings.len() + 1);
+    lines.push(format!("{:>4} {:>3} {:>12} {:>12} {:>12} {:>12}", "Year", "Day", "Parse", "Part 1", "Part 2", "Total"));
+    for timing in timings {
+        lines.push(format!("{:>4} {:>3} {:>12} {:>12} {:>12} {:>12}",
+            timing.year,
+            timing.day,
+            format_duration(Some(timing.parse)),
+            format_duration(timing.part(Part::One)),
+            format_duration(timing.part(Part::Two)),
+            format_duration(Some(timing.total()))));
+    }
+    lines.join("\n")
+}
+
+#[test]
+fn test_table() {
+    let timing = Timing {
+        year: 2022,
+        day: 5,
+        parse: Duration::from_millis(2),
+        parts: vec![(Part::Two, Duration::from_millis(1))],
+    };
+    assert_eq!(Duration::from_millis(3), timing.total());
+    let table = table(&[timing]);
+    let row = table.lines().nth(1).expect("Timing row");
+    assert_eq!(vec!["2022", "5", "2.00ms", "-", "1.00ms", "3.00ms"], row.split_whitespace().collect::<Vec<&str>>());
+}
@@ -0,0 +1,16 @@
+use aoc::answers::{check, discover};
+use aoc::puzzles::{self, workspace_root};
+
+#[test]
+fn test_expected_answers() {
+    let folders = discover(workspace_root()).expect("Puzzle folders");
+    assert!(!folders.is_empty());
+    let mut failures: Vec<String> = Vec::new();
+    for (year, day, folder) in folders {
+        match puzzles::find(year, day) {
+            Some(puzzle) => failures.extend(check(puzzle, &folder)),
+            None => failures.push(format!("{}: no registered puzzle", folder.display())),
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
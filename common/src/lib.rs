@@ -0,0 +1,143 @@
+//! Common definitions shared by all puzzles.
+
+#![warn(missing_docs)]
+
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// A puzzle part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    /// The first part.
+    One,
+    /// The second part, unlocked once the first one is solved.
+    Two
+}
+
+impl Part {
+    /// Both parts, in order.
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("Invalid part {}", s))
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => f.write_str("1"),
+            Part::Two => f.write_str("2"),
+        }
+    }
+}
+
+/// An error found while parsing a puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Column number, starting at 1.
+    pub column: usize,
+    /// The offending text.
+    pub text: String,
+    /// What went wrong.
+    pub message: String,
+}
+
+impl ParseError {
+
+    /// Creates an error at the given position.
+    pub fn new(line: usize, column: usize, text: &str, message: &str) -> ParseError {
+        ParseError {
+            line,
+            column,
+            text: text.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Creates an error about `token`, which must be a slice of `line_str`
+    /// so that its column can be computed.
+    pub fn in_line(line: usize, line_str: &str, token: &str, message: &str) -> ParseError {
+        let start = line_str.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| offset + token.len() <= line_str.len())
+            .unwrap_or(0);
+        ParseError::new(line, offset + 1, token, message)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {} `{}`", self.line, self.column, self.message, self.text)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Common behavior of puzzle solutions.
+///
+/// The input is parsed once, then both parts are solved from the parsed state.
+pub trait Solution: Sized {
+    /// Answer of the first part.
+    type Answer1: Display;
+    /// Answer of the second part.
+    type Answer2: Display;
+
+    /// Parses the whole puzzle input.
+    fn parse(input: &str) -> Result<Self, ParseError>;
+
+    /// Solves the first part.
+    fn part1(&self) -> Self::Answer1;
+
+    /// Solves the second part.
+    fn part2(&self) -> Self::Answer2;
+}
+
+/// A parsed solution whose answers are rendered as text, so that
+/// different puzzles can be handled uniformly.
+pub trait AnySolution {
+    /// Solves the given part.
+    fn answer(&self, part: Part) -> String;
+}
+
+impl<S: Solution> AnySolution for S {
+    fn answer(&self, part: Part) -> String {
+        match part {
+            Part::One => self.part1().to_string(),
+            Part::Two => self.part2().to_string(),
+        }
+    }
+}
+
+/// Parses the input of the solution `S` into a type-erased solution.
+pub fn parse_any<S: Solution + 'static>(input: &str) -> Result<Box<dyn AnySolution>, ParseError> {
+    let solution = S::parse(input)?;
+    Ok(Box::new(solution))
+}
+
+#[test]
+fn test_part_from_str() {
+    assert_eq!(Ok(Part::One), "1".parse::<Part>());
+    assert_eq!(Ok(Part::Two), "2".parse::<Part>());
+    assert!("3".parse::<Part>().is_err());
+}
+
+#[test]
+fn test_parse_error_in_line() {
+    let line = "move 4 from x to 2";
+    let error = ParseError::in_line(3, line, &line[12..13], "Invalid stack");
+    assert_eq!(13, error.column);
+    assert_eq!("line 3, column 13: Invalid stack `x`", error.to_string());
+}